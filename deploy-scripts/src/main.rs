@@ -1,5 +1,5 @@
 pub mod deployer;
-use anyhow::{bail, Context, Error};
+use anyhow::{anyhow, bail, Context, Error};
 use concordium_rust_sdk::{
     common::types::Amount,
     smart_contracts::{
@@ -20,9 +20,12 @@ use concordium_rust_sdk::{
 };
 use deployer::{DeployResult, Deployer, InitResult};
 use registry::{AtomicUpdateParam, ImportContractsParam};
+use serde::Deserialize;
+use staking_bank::InitParamsStakingBank;
 use std::{
     io::Cursor,
     path::{Path, PathBuf},
+    str::FromStr,
 };
 use structopt::{clap::AppSettings, StructOpt};
 use umbrella_feeds::InitParamsUmbrellaFeeds;
@@ -35,6 +38,51 @@ fn get_wasm_module(file: &Path) -> Result<WasmModule, Error> {
     Ok(wasm_module)
 }
 
+/// A validator entry of a `staking-bank/validators/<environment>.toml` file.
+#[derive(Deserialize)]
+struct ValidatorConfig {
+    /// The hex encoded Ed25519 public key of the validator.
+    public_key: String,
+    /// The URL of the validator.
+    url: String,
+}
+
+/// The content of a `staking-bank/validators/<environment>.toml` file.
+#[derive(Deserialize)]
+struct ValidatorsConfig {
+    validators: Vec<ValidatorConfig>,
+}
+
+/// Reads the validators from a given file path and returns them (together with the registry, the liveness window and the
+/// unbonding period in seconds) as the init parameter of the staking_bank contract.
+fn get_staking_bank_init_params(
+    file: &Path,
+    registry: ContractAddress,
+    liveness_window: u64,
+    unbonding_period: u64,
+) -> Result<InitParamsStakingBank, Error> {
+    let content = std::fs::read_to_string(file).context("Could not read the validators file")?;
+    let config: ValidatorsConfig =
+        toml::from_str(&content).context("Could not parse the validators file")?;
+
+    let validators = config
+        .validators
+        .into_iter()
+        .map(|validator| {
+            let public_key = contracts_common::PublicKeyEd25519::from_str(&validator.public_key)
+                .map_err(|_| anyhow!("Invalid validator public key `{}`", validator.public_key))?;
+            Ok((public_key, validator.url))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(InitParamsStakingBank {
+        validators,
+        registry,
+        liveness_window: contracts_common::Duration::from_seconds(liveness_window),
+        unbonding_period: contracts_common::Duration::from_seconds(unbonding_period),
+    })
+}
+
 /// Try to parse the return value into a type that implements [`Deserial`].
 /// Ensures that all bytes of the return value are read.
 pub fn parse_return_value<T: Deserial>(return_value: ReturnValue) -> ParseResult<T> {
@@ -100,6 +148,24 @@ enum Command {
             help = "Decimals for prices stored in the umbrella feeds contract."
         )]
         decimals: u8,
+        #[structopt(
+            long = "validators",
+            help = "Path to the validators file of the staking bank contract (e.g. --validators \
+                    ../staking-bank/validators/production.toml)."
+        )]
+        validators: PathBuf,
+        #[structopt(
+            long = "liveness_window",
+            default_value = "86400",
+            help = "Duration in seconds a validator can be inactive before it is jailed in the staking bank contract."
+        )]
+        liveness_window: u64,
+        #[structopt(
+            long = "unbonding_period",
+            default_value = "604800",
+            help = "Duration in seconds undelegated CCD is locked in the staking bank contract before it can be withdrawn."
+        )]
+        unbonding_period: u64,
     },
     #[structopt(
         name = "register",
@@ -207,6 +273,9 @@ async fn main() -> Result<(), Error> {
             key_file,
             required_signatures,
             decimals,
+            validators,
+            liveness_window,
+            unbonding_period,
         } => {
             // Setting up the connection to the node
             let endpoint = if url
@@ -269,11 +338,18 @@ async fn main() -> Result<(), Error> {
 
             print!("\nInitializing staking_bank contract....");
 
+            let input_parameter = get_staking_bank_init_params(
+                &validators,
+                init_result_registry_contract.contract_address,
+                liveness_window,
+                unbonding_period,
+            )?;
+
             let payload = InitContractPayload {
                 init_name: OwnedContractName::new("init_staking_bank".into())?,
                 amount: Amount::from_micro_ccd(0),
                 mod_ref: staking_bank_module_reference,
-                param: OwnedParameter::empty(),
+                param: OwnedParameter::from_serial(&input_parameter)?,
             };
 
             let init_result_staking_bank: InitResult = deployer
@@ -372,15 +448,15 @@ async fn main() -> Result<(), Error> {
 
             let mut deployer = Deployer::new(concordium_client, &key_file)?;
 
-            // Checking that the module reference is different to the staking_bank module reference registered in the registry
+            // Checking that the module reference is different from the staking_bank module reference registered in the registry
 
-            // Step 1: Getting the module reference from the new staking bank
+            // Step 1: Getting the module reference from the new staking bank contract
 
             let new_wasm_module = get_wasm_module(&new_staking_bank)?;
 
             let new_module_reference = new_wasm_module.get_module_ref();
 
-            // Step 2: Getting the module reference from the staking bank already registered in the registry
+            // Step 2: Getting the module reference from the staking bank contract already registered in the registry
 
             let bytes = contracts_common::to_bytes(&"StakingBank");
 
@@ -403,7 +479,7 @@ async fn main() -> Result<(), Error> {
                 .await
                 .context("Failed invoking instance")?;
 
-            let old_staking_contract: ContractAddress = match result.response {
+            let old_staking_bank_contract: ContractAddress = match result.response {
                 Success {
                     return_value,
                     events: _,
@@ -425,7 +501,7 @@ async fn main() -> Result<(), Error> {
 
             let info = deployer
                 .client
-                .get_instance_info(old_staking_contract, &BlockIdentifier::LastFinal)
+                .get_instance_info(old_staking_bank_contract, &BlockIdentifier::LastFinal)
                 .await
                 .context("Failed querying instance info")?;
 
@@ -434,48 +510,36 @@ async fn main() -> Result<(), Error> {
             if old_module_reference == new_module_reference {
                 bail!("Failed the new staking bank module reference has to be different from the old staking bank module reference.")
             } else {
-                // Deploying new staking_bank wasm modules
+                // Deploying new staking bank wasm modules
 
                 let new_staking_bank_module_reference =
                     deploy_module(&mut deployer.clone(), &new_staking_bank).await?;
 
-                // Initializing staking_bank
-
-                print!("\nInitializing new staking_bank contract....");
-
-                let payload = InitContractPayload {
-                    init_name: OwnedContractName::new("init_staking_bank".into())?,
-                    amount: Amount::from_micro_ccd(0),
-                    mod_ref: new_staking_bank_module_reference,
-                    param: OwnedParameter::empty(),
-                };
-
-                let init_result_staking_bank: InitResult = deployer
-                    .init_contract(payload, None, None)
-                    .await
-                    .context("Failed to initialize the new staking bank contract.")?;
-
-                // Updating staking bank address in registry contract
+                // Natively upgrade staking bank contract via registry (the contract address, the validators and the
+                // delegations are kept)
 
-                print!("\nUpdating staking bank address in resgistry contract....");
+                print!("\nNatively upgrade staking bank contract via registry....");
 
-                let bytes = contracts_common::to_bytes(&ImportContractsParam {
-                    entries: vec![init_result_staking_bank.contract_address],
+                let bytes = contracts_common::to_bytes(&AtomicUpdateParam {
+                    module: new_staking_bank_module_reference,
+                    migrate: None,
+                    contract_address: old_staking_bank_contract,
                 });
 
                 let update_payload = transactions::UpdateContractPayload {
                     amount: Amount::from_ccd(0),
                     address: registry_contract,
                     receive_name: OwnedReceiveName::new_unchecked(
-                        "registry.importContracts".to_string(),
+                        "registry.atomicUpdate".to_string(),
                     ),
                     message: bytes.try_into()?,
                 };
 
-                let _update_contract = deployer
-                    .update_contract(update_payload, None, None)
-                    .await
-                    .context("Failed to register the contract.")?;
+                let _update_contract =
+                    deployer
+                        .update_contract(update_payload, None, None)
+                        .await
+                        .context("Failed to natively upgrade the staking bank contract.")?;
             }
         }
         // Upgrading the umbrella_feeds contract
@@ -0,0 +1,462 @@
+//! # Example of a simplified collateralized lending market that values its collateral with the umbrella oracle.
+//!
+//! Accounts `deposit` CCD as collateral and `borrow` a synthetic USD balance against it (with 6 decimals). The
+//! collateral is valued with the up-to-date price of a price feed (such as `CCD-USD`) of the `UmbrellaFeeds` contract
+//! registered in the registry. Borrowing, withdrawing collateral of an indebted position and liquidating fail with
+//! `PriceNotUpToDate` if the heartbeat of the price data elapsed.
+//!
+//! A position can be borrowed up to the `collateral_factor` of its collateral value. Once its debt exceeds the
+//! `liquidation_threshold` of its collateral value (e.g. because the price dropped), anyone can `liquidate` it by
+//! repaying its debt with their synthetic USD balance. The liquidator receives collateral worth the repaid debt plus the
+//! `liquidation_bonus`.
+//!
+//! ATTENTION: This contract is an example of how to integrate the umbrella oracle. It does not accrue interest and has
+//! no protection against e.g. bad debt and is not meant to be used in production.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use concordium_std::*;
+use umbrella_consumer::{fetch_scaled, ConsumerError};
+
+/// The decimals of the synthetic USD balances and debts.
+pub const USD_DECIMALS: u8 = 6;
+
+/// The basis points of 100% (`collateral_factor`, `liquidation_threshold` and `liquidation_bonus`).
+pub const BASIS_POINTS: u16 = 10000;
+
+/// Micro CCD per CCD.
+const MICRO_CCD_PER_CCD: u128 = 1_000_000;
+
+/// The parameter type for the contract function `init`.
+#[derive(Serialize, SchemaType, Clone)]
+pub struct InitParamsLendingMarket {
+    /// The registry contract of the umbrella oracle protocol where the `UmbrellaFeeds` contract is looked up.
+    pub registry: ContractAddress,
+    /// The price feed of the collateral in USD (such as `CCD-USD`).
+    pub price_feed: String,
+    /// The share of the collateral value that can be borrowed (in basis points).
+    pub collateral_factor: u16,
+    /// The share of the collateral value above which a debt can be liquidated (in basis points).
+    pub liquidation_threshold: u16,
+    /// The share of the repaid debt that a liquidator receives on top in collateral (in basis points).
+    pub liquidation_bonus: u16,
+}
+
+/// The collateral and debt of an account.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Position {
+    /// The deposited CCD.
+    pub collateral: Amount,
+    /// The borrowed synthetic USD (with `USD_DECIMALS`).
+    pub debt: u128,
+}
+
+/// The state of the smart contract.
+#[derive(Serial, DeserialWithState)]
+#[concordium(state_parameter = "S")]
+struct State<S = StateApi> {
+    /// The registry contract of the umbrella oracle protocol.
+    registry: ContractAddress,
+    /// The price feed of the collateral in USD.
+    price_feed: String,
+    /// The share of the collateral value that can be borrowed (in basis points).
+    collateral_factor: u16,
+    /// The share of the collateral value above which a debt can be liquidated (in basis points).
+    liquidation_threshold: u16,
+    /// The share of the repaid debt that a liquidator receives on top in collateral (in basis points).
+    liquidation_bonus: u16,
+    /// The positions of the accounts.
+    positions: StateMap<AccountAddress, Position, S>,
+    /// The synthetic USD balances of the accounts (borrowed and not repaid yet).
+    balances: StateMap<AccountAddress, u128, S>,
+}
+
+/// Errors
+#[derive(Debug, PartialEq, Eq, Clone, Reject, Serialize, SchemaType)]
+pub enum CustomContractError {
+    /// Failed parsing the parameter.
+    #[from(ParseError)]
+    ParseParams, // -1
+    /// Failed because the price fetched from the oracle is not up to date.
+    PriceNotUpToDate, // -2
+    /// Failed to invoke a contract.
+    InvokeContractError, // -3
+    /// Failed because of an overflow.
+    Overflow, // -4
+    /// Failed because the sender is not an account.
+    OnlyAccount, // -5
+    /// Failed because the `collateral_factor` exceeds the `liquidation_threshold` or the `liquidation_threshold`
+    /// exceeds 100%.
+    InvalidParams, // -6
+    /// Failed because the debt would exceed the `collateral_factor` of the collateral value.
+    InsufficientCollateral, // -7
+    /// Failed because the synthetic USD balance is too small.
+    InsufficientBalance, // -8
+    /// Failed to liquidate a position whose debt does not exceed the `liquidation_threshold` of its collateral value.
+    PositionHealthy, // -9
+    /// Failed to transfer CCD to an account.
+    InvokeTransferError, // -10
+}
+
+/// Mapping errors of the umbrella consumer helper functions to CustomContractError.
+impl From<ConsumerError> for CustomContractError {
+    fn from(error: ConsumerError) -> Self {
+        match error {
+            ConsumerError::ParseReturnValue => Self::ParseParams,
+            ConsumerError::InvokeRegistryError | ConsumerError::InvokeUmbrellaFeedsError => {
+                Self::InvokeContractError
+            }
+            ConsumerError::PriceNotUpToDate => Self::PriceNotUpToDate,
+            ConsumerError::Overflow => Self::Overflow,
+        }
+    }
+}
+
+/// Mapping errors related to CCD transfers to CustomContractError.
+impl From<TransferError> for CustomContractError {
+    fn from(_te: TransferError) -> Self {
+        Self::InvokeTransferError
+    }
+}
+
+/// Init function that creates a new lending market. The `collateral_factor` can not exceed the
+/// `liquidation_threshold` and the `liquidation_threshold` can not exceed 100%.
+#[init(
+    contract = "lending_market",
+    parameter = "InitParamsLendingMarket",
+    error = "CustomContractError"
+)]
+fn init(ctx: &InitContext, state_builder: &mut StateBuilder) -> Result<State, CustomContractError> {
+    let params: InitParamsLendingMarket = ctx.parameter_cursor().get()?;
+
+    ensure!(
+        params.collateral_factor <= params.liquidation_threshold
+            && params.liquidation_threshold <= BASIS_POINTS,
+        CustomContractError::InvalidParams
+    );
+
+    Ok(State {
+        registry: params.registry,
+        price_feed: params.price_feed,
+        collateral_factor: params.collateral_factor,
+        liquidation_threshold: params.liquidation_threshold,
+        liquidation_bonus: params.liquidation_bonus,
+        positions: state_builder.new_map(),
+        balances: state_builder.new_map(),
+    })
+}
+
+/// Internal function that returns the account that invoked the entry point.
+fn sender_account(ctx: &ReceiveContext) -> Result<AccountAddress, CustomContractError> {
+    match ctx.sender() {
+        Address::Account(account) => Ok(account),
+        Address::Contract(_) => Err(CustomContractError::OnlyAccount),
+    }
+}
+
+/// Internal function that queries the up-to-date price of the collateral in USD (with `USD_DECIMALS`).
+fn collateral_price(host: &Host<State>, now: Timestamp) -> Result<u128, CustomContractError> {
+    let state = host.state();
+
+    Ok(fetch_scaled(
+        host,
+        &state.registry,
+        &state.price_feed,
+        now,
+        USD_DECIMALS,
+    )?)
+}
+
+/// Internal function that calculates the USD value (with `USD_DECIMALS`) of the collateral.
+fn collateral_value(collateral: Amount, price: u128) -> Result<u128, CustomContractError> {
+    let value = u128::from(collateral.micro_ccd)
+        .checked_mul(price)
+        .ok_or(CustomContractError::Overflow)?;
+
+    Ok(value / MICRO_CCD_PER_CCD)
+}
+
+/// Internal function that checks if the debt exceeds the share (in basis points) of the collateral value.
+fn exceeds(position: &Position, share: u16, price: u128) -> Result<bool, CustomContractError> {
+    let debt = position
+        .debt
+        .checked_mul(u128::from(BASIS_POINTS))
+        .ok_or(CustomContractError::Overflow)?;
+
+    let limit = collateral_value(position.collateral, price)?
+        .checked_mul(u128::from(share))
+        .ok_or(CustomContractError::Overflow)?;
+
+    Ok(debt > limit)
+}
+
+/// Internal function that returns the position of an account.
+fn position_of(host: &Host<State>, account: &AccountAddress) -> Position {
+    host.state()
+        .positions
+        .get(account)
+        .map(|position| *position)
+        .unwrap_or(Position {
+            collateral: Amount::zero(),
+            debt: 0,
+        })
+}
+
+/// Internal function that stores the position of an account (and removes empty positions).
+fn set_position(host: &mut Host<State>, account: AccountAddress, position: Position) {
+    let positions = &mut host.state_mut().positions;
+
+    if position.collateral == Amount::zero() && position.debt == 0 {
+        positions.remove(&account);
+    } else {
+        let _ = positions.insert(account, position);
+    }
+}
+
+/// Deposits the sent CCD as collateral of the sender.
+#[receive(
+    contract = "lending_market",
+    name = "deposit",
+    error = "CustomContractError",
+    payable,
+    mutable
+)]
+fn deposit(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    amount: Amount,
+) -> Result<(), CustomContractError> {
+    let sender = sender_account(ctx)?;
+
+    let mut position = position_of(host, &sender);
+
+    position.collateral = position
+        .collateral
+        .micro_ccd
+        .checked_add(amount.micro_ccd)
+        .map(Amount::from_micro_ccd)
+        .ok_or(CustomContractError::Overflow)?;
+
+    set_position(host, sender, position);
+
+    Ok(())
+}
+
+/// Withdraws collateral of the sender. If the sender has a debt, it can not exceed the `collateral_factor` of the
+/// remaining collateral value.
+#[receive(
+    contract = "lending_market",
+    name = "withdraw",
+    parameter = "Amount",
+    error = "CustomContractError",
+    mutable
+)]
+fn withdraw(ctx: &ReceiveContext, host: &mut Host<State>) -> Result<(), CustomContractError> {
+    let sender = sender_account(ctx)?;
+
+    let amount: Amount = ctx.parameter_cursor().get()?;
+
+    let mut position = position_of(host, &sender);
+
+    position.collateral = position
+        .collateral
+        .micro_ccd
+        .checked_sub(amount.micro_ccd)
+        .map(Amount::from_micro_ccd)
+        .ok_or(CustomContractError::InsufficientCollateral)?;
+
+    if position.debt > 0 {
+        let price = collateral_price(host, ctx.metadata().block_time())?;
+
+        ensure!(
+            !exceeds(&position, host.state().collateral_factor, price)?,
+            CustomContractError::InsufficientCollateral
+        );
+    }
+
+    set_position(host, sender, position);
+
+    host.invoke_transfer(&sender, amount)?;
+
+    Ok(())
+}
+
+/// Borrows synthetic USD (with `USD_DECIMALS`) against the collateral of the sender. The debt can not exceed the
+/// `collateral_factor` of the collateral value.
+#[receive(
+    contract = "lending_market",
+    name = "borrow",
+    parameter = "u128",
+    error = "CustomContractError",
+    mutable
+)]
+fn borrow(ctx: &ReceiveContext, host: &mut Host<State>) -> Result<(), CustomContractError> {
+    let sender = sender_account(ctx)?;
+
+    let amount: u128 = ctx.parameter_cursor().get()?;
+
+    let mut position = position_of(host, &sender);
+
+    position.debt = position
+        .debt
+        .checked_add(amount)
+        .ok_or(CustomContractError::Overflow)?;
+
+    let price = collateral_price(host, ctx.metadata().block_time())?;
+
+    ensure!(
+        !exceeds(&position, host.state().collateral_factor, price)?,
+        CustomContractError::InsufficientCollateral
+    );
+
+    set_position(host, sender, position);
+
+    let balances = &mut host.state_mut().balances;
+
+    let balance = balances.get(&sender).map(|balance| *balance).unwrap_or(0);
+
+    let _ = balances.insert(
+        sender,
+        balance
+            .checked_add(amount)
+            .ok_or(CustomContractError::Overflow)?,
+    );
+
+    Ok(())
+}
+
+/// Repays debt of the sender with its synthetic USD balance. At most the debt is repaid.
+#[receive(
+    contract = "lending_market",
+    name = "repay",
+    parameter = "u128",
+    error = "CustomContractError",
+    mutable
+)]
+fn repay(ctx: &ReceiveContext, host: &mut Host<State>) -> Result<(), CustomContractError> {
+    let sender = sender_account(ctx)?;
+
+    let amount: u128 = ctx.parameter_cursor().get()?;
+
+    let mut position = position_of(host, &sender);
+
+    let amount = amount.min(position.debt);
+
+    spend(host, &sender, amount)?;
+
+    position.debt -= amount;
+
+    set_position(host, sender, position);
+
+    Ok(())
+}
+
+/// Internal function that deducts synthetic USD from the balance of an account.
+fn spend(
+    host: &mut Host<State>,
+    account: &AccountAddress,
+    amount: u128,
+) -> Result<(), CustomContractError> {
+    let balances = &mut host.state_mut().balances;
+
+    let balance = balances.get(account).map(|balance| *balance).unwrap_or(0);
+
+    let balance = balance
+        .checked_sub(amount)
+        .ok_or(CustomContractError::InsufficientBalance)?;
+
+    if balance == 0 {
+        balances.remove(account);
+    } else {
+        let _ = balances.insert(*account, balance);
+    }
+
+    Ok(())
+}
+
+/// Liquidates the position of an account whose debt exceeds the `liquidation_threshold` of its collateral value. The
+/// sender repays the whole debt with its synthetic USD balance and receives collateral worth the debt plus the
+/// `liquidation_bonus` (at most the whole collateral). The remaining collateral stays in the liquidated position.
+#[receive(
+    contract = "lending_market",
+    name = "liquidate",
+    parameter = "AccountAddress",
+    error = "CustomContractError",
+    mutable
+)]
+fn liquidate(ctx: &ReceiveContext, host: &mut Host<State>) -> Result<(), CustomContractError> {
+    let sender = sender_account(ctx)?;
+
+    let account: AccountAddress = ctx.parameter_cursor().get()?;
+
+    let mut position = position_of(host, &account);
+
+    ensure!(position.debt > 0, CustomContractError::PositionHealthy);
+
+    let price = collateral_price(host, ctx.metadata().block_time())?;
+
+    ensure!(
+        exceeds(&position, host.state().liquidation_threshold, price)?,
+        CustomContractError::PositionHealthy
+    );
+
+    // The collateral worth the debt plus the liquidation bonus (all collateral if the price dropped to zero).
+    let seized = position
+        .debt
+        .checked_mul(u128::from(BASIS_POINTS) + u128::from(host.state().liquidation_bonus))
+        .and_then(|value| value.checked_mul(MICRO_CCD_PER_CCD))
+        .ok_or(CustomContractError::Overflow)?
+        .checked_div(
+            price
+                .checked_mul(u128::from(BASIS_POINTS))
+                .ok_or(CustomContractError::Overflow)?,
+        )
+        .unwrap_or(u128::MAX);
+
+    let seized = Amount::from_micro_ccd(
+        u64::try_from(seized)
+            .unwrap_or(u64::MAX)
+            .min(position.collateral.micro_ccd),
+    );
+
+    spend(host, &sender, position.debt)?;
+
+    position.debt = 0;
+    position.collateral = Amount::from_micro_ccd(position.collateral.micro_ccd - seized.micro_ccd);
+
+    set_position(host, account, position);
+
+    host.invoke_transfer(&sender, seized)?;
+
+    Ok(())
+}
+
+/// View function that returns the position of an account.
+#[receive(
+    contract = "lending_market",
+    name = "position",
+    parameter = "AccountAddress",
+    return_value = "Position"
+)]
+fn position(ctx: &ReceiveContext, host: &Host<State>) -> ReceiveResult<Position> {
+    let account: AccountAddress = ctx.parameter_cursor().get()?;
+
+    Ok(position_of(host, &account))
+}
+
+/// View function that returns the synthetic USD balance of an account.
+#[receive(
+    contract = "lending_market",
+    name = "balanceOf",
+    parameter = "AccountAddress",
+    return_value = "u128"
+)]
+fn balance_of(ctx: &ReceiveContext, host: &Host<State>) -> ReceiveResult<u128> {
+    let account: AccountAddress = ctx.parameter_cursor().get()?;
+
+    Ok(host
+        .state()
+        .balances
+        .get(&account)
+        .map(|balance| *balance)
+        .unwrap_or(0))
+}
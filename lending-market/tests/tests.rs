@@ -0,0 +1,644 @@
+//! Tests for the lending market example.
+use concordium_smart_contract_testing::*;
+use concordium_std::{PublicKeyEd25519, SignatureEd25519};
+use lending_market::{InitParamsLendingMarket, Position};
+use registry::ImportContractsParam;
+use staking_bank::InitParamsStakingBank;
+use umbrella_feeds::{InitParamsUmbrellaFeeds, Message, PriceData, UpdateParams};
+
+/// The tests accounts.
+const ALICE: AccountAddress = AccountAddress([0; 32]);
+const ALICE_ADDR: Address = Address::Account(ALICE);
+const BOB: AccountAddress = AccountAddress([1; 32]);
+
+const ACC_INITIAL_BALANCE: Amount = Amount::from_ccd(10000);
+const SIGNER: Signer = Signer::with_one_key();
+
+/// The price feed of the collateral (the `umbrella_feeds` contract uses 4 decimals).
+const PRICE_FEED: &str = "CCD-USD";
+
+/// The heartbeat of the price data in seconds.
+const HEARTBEAT: u64 = 60;
+
+/// One synthetic USD (with 6 decimals).
+const USD: u128 = 1_000_000;
+
+// The validator registered in the `staking_bank` contract.
+// ATTENTION: Use a different key in production. This key and its private key is exposed and used for testing here.
+// Private key: 8ECA45107A878FB879B84401084B55AD4919FC0F7D14E8915D8A5989B1AE1C01
+const VALIDATOR: PublicKeyEd25519 = PublicKeyEd25519([
+    120, 154, 141, 6, 248, 239, 77, 224, 80, 62, 139, 136, 211, 204, 105, 208, 26, 11, 2, 208, 195,
+    253, 29, 192, 126, 199, 208, 39, 69, 4, 246, 32,
+]);
+
+const DUMMY_SIGNATURE: SignatureEd25519 = SignatureEd25519([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+]);
+
+/// Returns the reject reason of a failed invoke if the contract rejected on its own.
+fn reject_reason(error: &ContractInvokeError) -> Option<i32> {
+    match &error.kind {
+        ContractInvokeErrorKind::ExecutionError {
+            failure_kind: InvokeFailure::ContractReject { code, .. },
+        } => Some(*code),
+        _ => None,
+    }
+}
+
+/// Test depositing, borrowing, repaying and withdrawing.
+#[test]
+fn test_deposit_and_borrow() {
+    let (mut chain, umbrella_feeds, lending_market) = initialize_chain_and_contract();
+
+    deposit(&mut chain, ALICE, lending_market, Amount::from_ccd(1000))
+        .expect("Should be able to deposit");
+
+    // Checking that borrowing fails without the price feed.
+
+    let update = borrow(&mut chain, ALICE, lending_market, 100 * USD)
+        .expect_err("Should fail to borrow without the price feed");
+
+    assert_eq!(reject_reason(&update), Some(-3));
+
+    // 1 CCD = 1.0000 USD
+    set_price(&mut chain, umbrella_feeds, 10000);
+
+    // Checking that at most 75% of the collateral value can be borrowed.
+
+    let update = borrow(&mut chain, ALICE, lending_market, 750 * USD + 1)
+        .expect_err("Should fail to borrow more than the collateral factor");
+
+    assert_eq!(reject_reason(&update), Some(-7));
+
+    borrow(&mut chain, ALICE, lending_market, 700 * USD).expect("Should be able to borrow");
+
+    assert_eq!(
+        get_position(&chain, lending_market, ALICE),
+        Position {
+            collateral: Amount::from_ccd(1000),
+            debt: 700 * USD,
+        }
+    );
+    assert_eq!(get_balance(&chain, lending_market, ALICE), 700 * USD);
+
+    // Checking that the collateral of an indebted position can only be withdrawn down to the collateral factor.
+
+    let update = update_lending_market(
+        &mut chain,
+        ALICE,
+        lending_market,
+        "withdraw",
+        OwnedParameter::from_serial(&Amount::from_ccd(100)).expect("Serialize parameter"),
+        Amount::zero(),
+    )
+    .expect_err("Should fail to withdraw collateral that is needed for the debt");
+
+    assert_eq!(reject_reason(&update), Some(-7));
+
+    update_lending_market(
+        &mut chain,
+        ALICE,
+        lending_market,
+        "repay",
+        OwnedParameter::from_serial(&(200 * USD)).expect("Serialize parameter"),
+        Amount::zero(),
+    )
+    .expect("Should be able to repay");
+
+    update_lending_market(
+        &mut chain,
+        ALICE,
+        lending_market,
+        "withdraw",
+        OwnedParameter::from_serial(&Amount::from_ccd(100)).expect("Serialize parameter"),
+        Amount::zero(),
+    )
+    .expect("Should be able to withdraw");
+
+    assert_eq!(
+        get_position(&chain, lending_market, ALICE),
+        Position {
+            collateral: Amount::from_ccd(900),
+            debt: 500 * USD,
+        }
+    );
+    assert_eq!(get_balance(&chain, lending_market, ALICE), 500 * USD);
+    assert_eq!(
+        chain.contract_balance(lending_market),
+        Some(Amount::from_ccd(900))
+    );
+
+    // Checking that a healthy position can not be liquidated.
+
+    let update = liquidate(&mut chain, BOB, lending_market, ALICE)
+        .expect_err("Should fail to liquidate a healthy position");
+
+    assert_eq!(reject_reason(&update), Some(-9));
+}
+
+/// Test liquidating a position after the price of the collateral dropped.
+#[test]
+fn test_price_drop_liquidation() {
+    let (mut chain, umbrella_feeds, lending_market) = initialize_chain_and_contract();
+
+    // 1 CCD = 1.0000 USD
+    set_price(&mut chain, umbrella_feeds, 10000);
+
+    open_positions(&mut chain, lending_market);
+
+    let update = liquidate(&mut chain, BOB, lending_market, ALICE)
+        .expect_err("Should fail to liquidate a healthy position");
+
+    assert_eq!(reject_reason(&update), Some(-9));
+
+    // 1 CCD = 0.8000 USD, the debt of ALICE (700 USD) exceeds 80% of the collateral value (800 USD).
+    set_price(&mut chain, umbrella_feeds, 8000);
+
+    let balance_before = chain.account_balance_available(BOB).expect("BOB exists");
+
+    let update =
+        liquidate(&mut chain, BOB, lending_market, ALICE).expect("Should be able to liquidate");
+
+    // BOB receives collateral worth the debt plus the 5% liquidation bonus: 700 USD * 1.05 / 0.8 = 918.75 CCD.
+    let seized = Amount::from_micro_ccd(918_750_000);
+
+    assert_eq!(
+        chain.account_balance_available(BOB),
+        Some(balance_before + seized - update.transaction_fee)
+    );
+    assert_eq!(
+        get_position(&chain, lending_market, ALICE),
+        Position {
+            collateral: Amount::from_ccd(1000) - seized,
+            debt: 0,
+        }
+    );
+    assert_eq!(get_balance(&chain, lending_market, BOB), 0);
+    assert_eq!(get_balance(&chain, lending_market, ALICE), 700 * USD);
+    assert_eq!(
+        chain.contract_balance(lending_market),
+        Some(Amount::from_ccd(6000) - seized)
+    );
+
+    // Checking that the liquidated position can not be liquidated again.
+
+    let update = liquidate(&mut chain, BOB, lending_market, ALICE)
+        .expect_err("Should fail to liquidate a position without debt");
+
+    assert_eq!(reject_reason(&update), Some(-9));
+}
+
+/// Test that positions can not be liquidated with a stale price.
+#[test]
+fn test_stale_price_liquidation() {
+    let (mut chain, umbrella_feeds, lending_market) = initialize_chain_and_contract();
+
+    // 1 CCD = 1.0000 USD
+    set_price(&mut chain, umbrella_feeds, 10000);
+
+    open_positions(&mut chain, lending_market);
+
+    // 1 CCD = 0.8000 USD
+    set_price(&mut chain, umbrella_feeds, 8000);
+
+    // The heartbeat of the price data elapses.
+    chain
+        .tick_block_time(Duration::from_seconds(HEARTBEAT))
+        .expect("Should be able to advance the block time");
+
+    // Checking that the position can not be liquidated and no collateral can be borrowed against with a stale price.
+
+    let update = liquidate(&mut chain, BOB, lending_market, ALICE)
+        .expect_err("Should fail to liquidate with a stale price");
+
+    assert_eq!(reject_reason(&update), Some(-2));
+
+    let update = borrow(&mut chain, BOB, lending_market, USD)
+        .expect_err("Should fail to borrow with a stale price");
+
+    assert_eq!(reject_reason(&update), Some(-2));
+
+    assert_eq!(
+        get_position(&chain, lending_market, ALICE),
+        Position {
+            collateral: Amount::from_ccd(1000),
+            debt: 700 * USD,
+        }
+    );
+
+    // The position can be liquidated once the price is updated again.
+    set_price(&mut chain, umbrella_feeds, 8000);
+
+    liquidate(&mut chain, BOB, lending_market, ALICE).expect("Should be able to liquidate");
+
+    assert_eq!(get_position(&chain, lending_market, ALICE).debt, 0);
+}
+
+/// ALICE deposits 1000 CCD and borrows 700 USD. BOB deposits 5000 CCD and borrows 700 USD (to be able to liquidate
+/// ALICE).
+fn open_positions(chain: &mut Chain, lending_market: ContractAddress) {
+    deposit(chain, ALICE, lending_market, Amount::from_ccd(1000))
+        .expect("Should be able to deposit");
+    borrow(chain, ALICE, lending_market, 700 * USD).expect("Should be able to borrow");
+
+    deposit(chain, BOB, lending_market, Amount::from_ccd(5000)).expect("Should be able to deposit");
+    borrow(chain, BOB, lending_market, 700 * USD).expect("Should be able to borrow");
+}
+
+/// Invokes an entry point of the lending market contract.
+fn update_lending_market(
+    chain: &mut Chain,
+    sender: AccountAddress,
+    lending_market: ContractAddress,
+    entrypoint: &str,
+    message: OwnedParameter,
+    amount: Amount,
+) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+    chain.contract_update(
+        SIGNER,
+        sender,
+        Address::Account(sender),
+        Energy::from(20000),
+        UpdateContractPayload {
+            amount,
+            address: lending_market,
+            receive_name: OwnedReceiveName::new_unchecked(format!("lending_market.{entrypoint}")),
+            message,
+        },
+    )
+}
+
+/// Invokes the `deposit` entry point of the lending market contract.
+fn deposit(
+    chain: &mut Chain,
+    sender: AccountAddress,
+    lending_market: ContractAddress,
+    amount: Amount,
+) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+    update_lending_market(
+        chain,
+        sender,
+        lending_market,
+        "deposit",
+        OwnedParameter::empty(),
+        amount,
+    )
+}
+
+/// Invokes the `borrow` entry point of the lending market contract.
+fn borrow(
+    chain: &mut Chain,
+    sender: AccountAddress,
+    lending_market: ContractAddress,
+    amount: u128,
+) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+    update_lending_market(
+        chain,
+        sender,
+        lending_market,
+        "borrow",
+        OwnedParameter::from_serial(&amount).expect("Serialize parameter"),
+        Amount::zero(),
+    )
+}
+
+/// Invokes the `liquidate` entry point of the lending market contract.
+fn liquidate(
+    chain: &mut Chain,
+    sender: AccountAddress,
+    lending_market: ContractAddress,
+    account: AccountAddress,
+) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+    update_lending_market(
+        chain,
+        sender,
+        lending_market,
+        "liquidate",
+        OwnedParameter::from_serial(&account).expect("Serialize parameter"),
+        Amount::zero(),
+    )
+}
+
+/// Invokes the `position` entry point of the lending market contract.
+fn get_position(
+    chain: &Chain,
+    lending_market: ContractAddress,
+    account: AccountAddress,
+) -> Position {
+    let invoke = chain
+        .contract_invoke(
+            ALICE,
+            ALICE_ADDR,
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "lending_market.position".to_string(),
+                ),
+                address: lending_market,
+                message: OwnedParameter::from_serial(&account).expect("Serialize parameter"),
+            },
+        )
+        .expect("Invoke position");
+
+    invoke.parse_return_value().expect("View return value")
+}
+
+/// Invokes the `balanceOf` entry point of the lending market contract.
+fn get_balance(chain: &Chain, lending_market: ContractAddress, account: AccountAddress) -> u128 {
+    let invoke = chain
+        .contract_invoke(
+            ALICE,
+            ALICE_ADDR,
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "lending_market.balanceOf".to_string(),
+                ),
+                address: lending_market,
+                message: OwnedParameter::from_serial(&account).expect("Serialize parameter"),
+            },
+        )
+        .expect("Invoke balanceOf");
+
+    invoke.parse_return_value().expect("View return value")
+}
+
+/// Stores the price of the collateral in the `umbrella_feeds` contract. The block time is advanced by one second, so
+/// the price data is newer than the previously stored price data.
+fn set_price(chain: &mut Chain, umbrella_feeds_contract_address: ContractAddress, price: u128) {
+    chain
+        .tick_block_time(Duration::from_seconds(1))
+        .expect("Should be able to advance the block time");
+
+    let price_data = PriceData {
+        data: 0,
+        heartbeat: HEARTBEAT,
+        timestamp: chain.block_time(),
+        price,
+    };
+
+    update_umbrella_feeds_price(
+        chain,
+        umbrella_feeds_contract_address,
+        String::from(PRICE_FEED),
+        price_data,
+    );
+}
+
+/// Signs and stores the price data of a price feed in the `umbrella_feeds` contract.
+fn update_umbrella_feeds_price(
+    chain: &mut Chain,
+    umbrella_feeds_contract_address: ContractAddress,
+    feed_name: String,
+    price_data: PriceData,
+) {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    // The private key has to associated with the public key that is registered in
+    // the `staking_bank` contract (`VALIDATOR`).
+    // ATTENTION: Do not use this private key in production.
+    let signing_key = SigningKey::from_bytes(&[
+        142, 202, 69, 16, 122, 135, 143, 184, 121, 184, 68, 1, 8, 75, 85, 173, 73, 25, 252, 15,
+        125, 20, 232, 145, 93, 138, 89, 137, 177, 174, 28, 1,
+    ]);
+
+    let verifying_key = signing_key.verifying_key();
+
+    // The `viewMessageHash` function uses the input parameter `UpdateParams`.
+    // The `UpdateParams` type includes a `signers_and_signatures` field.
+    // Because this field (`signers_and_signatures`) is not
+    // read in the `viewMessageHash` function, any value can be used and we choose
+    // to use `DUMMY_SIGNATURE` and `verifying_key` in the test case below.
+    let mut param = UpdateParams {
+        signers_and_signatures: vec![(PublicKeyEd25519(verifying_key.to_bytes()), DUMMY_SIGNATURE)],
+        message: Message {
+            contract_address: umbrella_feeds_contract_address,
+            timestamp: Timestamp::from_timestamp_millis(1000000000000),
+            price_feed: vec![(feed_name, price_data)],
+        },
+    };
+
+    // Get the message hash to be signed.
+    let invoke = chain
+        .contract_invoke(
+            ALICE,
+            ALICE_ADDR,
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: umbrella_feeds_contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.viewMessageHash".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query viewMessageHash");
+
+    // Generate signature.
+    let signature = signing_key.sign(&invoke.return_value);
+
+    // Add signature to the input parameter.
+    param.signers_and_signatures = vec![(
+        PublicKeyEd25519(verifying_key.to_bytes()),
+        SignatureEd25519(signature.to_bytes()),
+    )];
+
+    // Updating price data in umbrella_feeds contract.
+    let _update: ContractInvokeSuccess = chain
+        .contract_update(
+            SIGNER,
+            ALICE,
+            Address::Account(ALICE),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: umbrella_feeds_contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to update the price in the umbrella oracle protocol");
+}
+
+/// Initializes a new `umbrella_feeds` contract and imports it into the `registry` contract (replacing the previously
+/// registered `umbrella_feeds` contract).
+fn initialize_umbrella_feeds(
+    chain: &mut Chain,
+    module_reference: ModuleReference,
+    registry: ContractAddress,
+    staking_bank: ContractAddress,
+) -> ContractAddress {
+    let input_parameter = InitParamsUmbrellaFeeds {
+        registry,
+        required_signatures: 1,
+        staking_bank,
+        decimals: 4,
+    };
+
+    let initialization_umbrella_feeds = chain
+        .contract_init(
+            Signer::with_one_key(),
+            ALICE,
+            Energy::from(10000),
+            InitContractPayload {
+                amount: Amount::zero(),
+                mod_ref: module_reference,
+                init_name: OwnedContractName::new_unchecked("init_umbrella_feeds".to_string()),
+                param: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`InitContractsParam` should be a valid inut parameter"),
+            },
+        )
+        .expect("Initialization of `umbrella_feeds` should always succeed");
+
+    let input_parameter = ImportContractsParam {
+        entries: vec![initialization_umbrella_feeds.contract_address],
+    };
+
+    // Importing the `umbrella_feeds` contract into the `registry` contract.
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ALICE,
+            Address::Account(ALICE),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.importContracts".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect("Should be able to importContracts");
+
+    initialization_umbrella_feeds.contract_address
+}
+
+/// Setup the umbrella oracle protocol and the lending_market contract.
+///
+/// Create accounts for ALICE and BOB. Returns the addresses of the umbrella feeds and the lending market contracts.
+fn initialize_chain_and_contract() -> (Chain, ContractAddress, ContractAddress) {
+    let mut chain = Chain::builder()
+        .build()
+        .expect("Expect setting up the chain");
+
+    // Create accounts on the chain.
+    chain.create_account(Account::new(ALICE, ACC_INITIAL_BALANCE));
+    chain.create_account(Account::new(BOB, ACC_INITIAL_BALANCE));
+
+    // Load and deploy the Umbrella oracle protocol.
+
+    // Deploying 'registry' contract
+    let deployment_registry = chain
+        .module_deploy_v1(
+            Signer::with_one_key(),
+            ALICE,
+            module_load_v1("../registry/registry.wasm.v1")
+                .expect("`registry.wasm.v1` module should be loaded"),
+        )
+        .expect("`registry.wasm.v1` deployment should always succeed");
+
+    let initialization_registry = chain
+        .contract_init(
+            Signer::with_one_key(),
+            ALICE,
+            Energy::from(10000),
+            InitContractPayload {
+                amount: Amount::zero(),
+                mod_ref: deployment_registry.module_reference,
+                init_name: OwnedContractName::new_unchecked("init_registry".to_string()),
+                param: OwnedParameter::empty(),
+            },
+        )
+        .expect("Initialization of `registry` should always succeed");
+
+    // Deploying 'staking_bank' contract
+    let deployment_staking_bank = chain
+        .module_deploy_v1(
+            Signer::with_one_key(),
+            ALICE,
+            module_load_v1("../staking-bank/staking_bank.wasm.v1")
+                .expect("`staking_bank.wasm.v1` module should be loaded"),
+        )
+        .expect("`staking_bank.wasm.v1` deployment should always succeed");
+
+    let input_parameter = InitParamsStakingBank {
+        validators: vec![(VALIDATOR, String::from("https://validator.dev.umb.network"))],
+        registry: initialization_registry.contract_address,
+        liveness_window: Duration::from_seconds(3600),
+        unbonding_period: Duration::from_seconds(86400),
+    };
+
+    let initialization_staking_bank = chain
+        .contract_init(
+            Signer::with_one_key(),
+            ALICE,
+            Energy::from(10000),
+            InitContractPayload {
+                amount: Amount::zero(),
+                mod_ref: deployment_staking_bank.module_reference,
+                init_name: OwnedContractName::new_unchecked("init_staking_bank".to_string()),
+                param: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`InitParamsStakingBank` should be a valid inut parameter"),
+            },
+        )
+        .expect("Initialization of `staking_bank` should always succeed");
+
+    // Deploying 'umbrella_feeds' contract
+    let deployment = chain
+        .module_deploy_v1(
+            Signer::with_one_key(),
+            ALICE,
+            module_load_v1("../umbrella-feeds/umbrella_feeds.wasm.v1")
+                .expect("`Umbrella_feeds.wasm.v1` module should be loaded"),
+        )
+        .expect("`Umbrella_feeds.wasm.v1` deployment should always succeed");
+
+    let umbrella_feeds_contract_address = initialize_umbrella_feeds(
+        &mut chain,
+        deployment.module_reference,
+        initialization_registry.contract_address,
+        initialization_staking_bank.contract_address,
+    );
+
+    // Load and deploy the module.
+    let module = module_load_v1("./lending_market.wasm.v1").expect("Module exists");
+    let deployment = chain
+        .module_deploy_v1(SIGNER, ALICE, module)
+        .expect("Deploy valid module");
+
+    let input_parameter = InitParamsLendingMarket {
+        registry: initialization_registry.contract_address,
+        price_feed: String::from(PRICE_FEED),
+        collateral_factor: 7500,
+        liquidation_threshold: 8000,
+        liquidation_bonus: 500,
+    };
+
+    // Initialize the lending market contract.
+    let init = chain
+        .contract_init(
+            SIGNER,
+            ALICE,
+            Energy::from(10000),
+            InitContractPayload {
+                amount: Amount::zero(),
+                mod_ref: deployment.module_reference,
+                init_name: OwnedContractName::new_unchecked("init_lending_market".to_string()),
+                param: OwnedParameter::from_serial(&input_parameter).expect("Serialize parameter"),
+            },
+        )
+        .expect("Initialize lending market contract");
+
+    (
+        chain,
+        umbrella_feeds_contract_address,
+        init.contract_address,
+    )
+}
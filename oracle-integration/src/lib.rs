@@ -2,29 +2,9 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use concordium_std::*;
-#[cfg(any(feature = "production", feature = "development", feature = "local"))]
-use umbrella_feeds::PriceData;
-
-/// Registry contract address in the integration test cases.
-#[cfg(feature = "local")]
-const UMBRELLA_REGISTRY_CONTRACT: ContractAddress = ContractAddress {
-    index: 1,
-    subindex: 0,
-};
-
-/// Attention: The umbrella oracle is not deployed on mainnet at the time of
-/// writing. https://umbrella-network.readme.io/docs/concordium-integration-examples
-#[cfg(feature = "production")]
-const UMBRELLA_REGISTRY_CONTRACT: ContractAddress = ContractAddress {
-    index: 0,
-    subindex: 0,
-};
-
-/// Registry contract address on Concordium testnet.
-#[cfg(feature = "development")]
-const UMBRELLA_REGISTRY_CONTRACT: ContractAddress = ContractAddress {
-    index: 7542,
-    subindex: 0,
+use umbrella_consumer::{
+    fetch_with_fallback, resolve_umbrella_feeds, ConsumerError, FallbackSources, PriceData,
+    PriceSource, SourcedPriceData,
 };
 
 /// The return_parameter of the contract function `prices`.
@@ -35,14 +15,36 @@ pub struct Prices {
     pub prices: Vec<(String, u128)>,
 }
 
+/// The parameter type for the contract function `setFallback` and the return value of the contract function `fallback`.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FallbackParams {
+    /// A secondary `UmbrellaFeeds` contract that is read if the price can not be read from the `UmbrellaFeeds` contract
+    /// registered in the registry (`None` to skip this source).
+    pub secondary_umbrella_feeds: Option<ContractAddress>,
+    /// The maximum age of the last known good price data (measured from its timestamp) that is used if the price can
+    /// not be read from any `UmbrellaFeeds` contract (zero to skip this source).
+    pub max_cache_age: Duration,
+}
+
 /// The state of the smart contract.
 #[derive(Serial, DeserialWithState)]
 #[concordium(state_parameter = "S")]
 struct State<S = StateApi> {
-    /// Map storing for each price feed name (such as `ETH-USDC`) its
-    /// corresponding price. A price was last updated whenever the
-    /// `update_price` function was invoked with the given price feed name.
-    last_price_update: StateMap<String, u128, S>,
+    /// The owner of this contract (the account that initialized it). It can update the registry contract address.
+    owner: AccountAddress,
+    /// The registry contract of the umbrella oracle protocol where the `UmbrellaFeeds` contract is looked up.
+    registry: ContractAddress,
+    /// The `UmbrellaFeeds` contract address resolved from the registry (`None` until it is resolved for the first time
+    /// or after the registry got updated).
+    umbrella_feeds: Option<ContractAddress>,
+    /// A secondary `UmbrellaFeeds` contract (`None` if it is not set).
+    secondary_umbrella_feeds: Option<ContractAddress>,
+    /// The maximum age of the last known good price data.
+    max_cache_age: Duration,
+    /// Map storing for each price feed name (such as `ETH-USDC`) its last known good price data, i.e. the price data
+    /// read from an `UmbrellaFeeds` contract the last time the `update_price` function was invoked with the given price
+    /// feed name.
+    last_known_good: StateMap<String, PriceData, S>,
 }
 
 /// Errors
@@ -58,23 +60,150 @@ pub enum CustomContractError {
     InvokeContractError, // -3
     /// Failed because the timestamp overflowed.
     Overflow, // -4
+    /// Failed because of unauthorized invoke of the entry point.
+    Unauthorized, // -5
 }
 
-/// Mapping errors related to contract invocations to CustomContractError.
-impl<T> From<CallContractError<T>> for CustomContractError {
-    fn from(_cce: CallContractError<T>) -> Self {
-        Self::InvokeContractError
+/// Mapping errors of the umbrella consumer helper functions to CustomContractError.
+impl From<ConsumerError> for CustomContractError {
+    fn from(error: ConsumerError) -> Self {
+        match error {
+            ConsumerError::ParseReturnValue => Self::ParseParams,
+            ConsumerError::InvokeRegistryError | ConsumerError::InvokeUmbrellaFeedsError => {
+                Self::InvokeContractError
+            }
+            ConsumerError::PriceNotUpToDate => Self::PriceNotUpToDate,
+            ConsumerError::Overflow => Self::Overflow,
+        }
     }
 }
 
-/// Init function that creates a new contract.
-#[init(contract = "smart_contract_oracle_integration")]
-fn init(_ctx: &InitContext, state_builder: &mut StateBuilder) -> InitResult<State> {
+/// Init function that creates a new contract. It takes the registry contract address of the umbrella oracle protocol
+/// (e.g. `<7542,0>` on testnet), so the same module can be used for every environment.
+#[init(
+    contract = "smart_contract_oracle_integration",
+    parameter = "ContractAddress"
+)]
+fn init(ctx: &InitContext, state_builder: &mut StateBuilder) -> InitResult<State> {
+    let registry: ContractAddress = ctx.parameter_cursor().get()?;
+
     Ok(State {
-        last_price_update: state_builder.new_map(),
+        owner: ctx.init_origin(),
+        registry,
+        umbrella_feeds: None,
+        secondary_umbrella_feeds: None,
+        max_cache_age: Duration::from_millis(0),
+        last_known_good: state_builder.new_map(),
     })
 }
 
+/// The owner can update the registry contract address. The cached `UmbrellaFeeds` contract address is cleared and
+/// resolved from the new registry with the next price update.
+#[receive(
+    contract = "smart_contract_oracle_integration",
+    name = "setRegistry",
+    parameter = "ContractAddress",
+    error = "CustomContractError",
+    mutable
+)]
+fn set_registry(ctx: &ReceiveContext, host: &mut Host<State>) -> Result<(), CustomContractError> {
+    ensure!(
+        ctx.sender().matches_account(&host.state().owner),
+        CustomContractError::Unauthorized
+    );
+
+    let registry: ContractAddress = ctx.parameter_cursor().get()?;
+
+    let state = host.state_mut();
+    state.registry = registry;
+    state.umbrella_feeds = None;
+
+    Ok(())
+}
+
+/// The owner can set the secondary `UmbrellaFeeds` contract and the maximum age of the last known good price data that
+/// are used by `update_price` if the price can not be read from the `UmbrellaFeeds` contract registered in the
+/// registry. Both sources are disabled after `init`.
+#[receive(
+    contract = "smart_contract_oracle_integration",
+    name = "setFallback",
+    parameter = "FallbackParams",
+    error = "CustomContractError",
+    mutable
+)]
+fn set_fallback(ctx: &ReceiveContext, host: &mut Host<State>) -> Result<(), CustomContractError> {
+    ensure!(
+        ctx.sender().matches_account(&host.state().owner),
+        CustomContractError::Unauthorized
+    );
+
+    let params: FallbackParams = ctx.parameter_cursor().get()?;
+
+    let state = host.state_mut();
+    state.secondary_umbrella_feeds = params.secondary_umbrella_feeds;
+    state.max_cache_age = params.max_cache_age;
+
+    Ok(())
+}
+
+/// View function that returns the secondary `UmbrellaFeeds` contract and the maximum age of the last known good price
+/// data.
+#[receive(
+    contract = "smart_contract_oracle_integration",
+    name = "fallback",
+    return_value = "FallbackParams"
+)]
+fn fallback(_ctx: &ReceiveContext, host: &Host<State>) -> ReceiveResult<FallbackParams> {
+    let state = host.state();
+
+    Ok(FallbackParams {
+        secondary_umbrella_feeds: state.secondary_umbrella_feeds,
+        max_cache_age: state.max_cache_age,
+    })
+}
+
+/// View function that returns the registry contract address.
+#[receive(
+    contract = "smart_contract_oracle_integration",
+    name = "registry",
+    return_value = "ContractAddress"
+)]
+fn registry(_ctx: &ReceiveContext, host: &Host<State>) -> ReceiveResult<ContractAddress> {
+    Ok(host.state().registry)
+}
+
+/// View function that returns the cached `UmbrellaFeeds` contract address (`None` if it was not resolved yet).
+#[receive(
+    contract = "smart_contract_oracle_integration",
+    name = "umbrellaFeeds",
+    return_value = "Option<ContractAddress>"
+)]
+fn umbrella_feeds(
+    _ctx: &ReceiveContext,
+    host: &Host<State>,
+) -> ReceiveResult<Option<ContractAddress>> {
+    Ok(host.state().umbrella_feeds)
+}
+
+/// Anyone can refresh the cached `UmbrellaFeeds` contract address with the address currently registered in the
+/// registry (e.g. after the registry logged a `ContractReplaced` event for the `UmbrellaFeeds` name).
+#[receive(
+    contract = "smart_contract_oracle_integration",
+    name = "refreshUmbrellaFeeds",
+    error = "CustomContractError",
+    mutable
+)]
+fn refresh_umbrella_feeds(
+    _ctx: &ReceiveContext,
+    host: &mut Host<State>,
+) -> Result<(), CustomContractError> {
+    let umbrella_feeds = resolve_umbrella_feeds(host, &host.state().registry)?;
+
+    host.state_mut().umbrella_feeds = Some(umbrella_feeds);
+
+    Ok(())
+}
+
 /// View function that returns the content of the state.
 #[receive(
     contract = "smart_contract_oracle_integration",
@@ -84,65 +213,63 @@ fn init(_ctx: &InitContext, state_builder: &mut StateBuilder) -> InitResult<Stat
 fn prices(_ctx: &ReceiveContext, host: &Host<State>) -> ReceiveResult<Vec<(String, u128)>> {
     let prices: Vec<(String, u128)> = host
         .state()
-        .last_price_update
+        .last_known_good
         .iter()
-        .map(|(a, b)| ((*a).clone(), *b))
+        .map(|(a, b)| ((*a).clone(), b.price))
         .collect();
     Ok(prices)
 }
 
-/// Receive function to update the prices in the contract state.
-#[cfg(any(feature = "production", feature = "development", feature = "local"))]
+/// Receive function to update the prices in the contract state. The `UmbrellaFeeds` contract address is looked up in
+/// the registry on every update and the cached address is refreshed if the registry entry changed (the cached address is
+/// only used if the registry can not be queried). The up-to-date price is read from the following sources in order and
+/// the source that was used is returned:
+/// - the `UmbrellaFeeds` contract registered in the registry (`Primary`),
+/// - the secondary `UmbrellaFeeds` contract (`Secondary`),
+/// - the last known good price data of the price feed if it is younger than the maximum cache age (`LastKnownGood`).
+///
+/// If no source is available, the error of the `UmbrellaFeeds` contract is returned.
 #[receive(
     contract = "smart_contract_oracle_integration",
     name = "update_price",
     parameter = "String",
+    return_value = "PriceSource",
     error = "CustomContractError",
     mutable
 )]
-fn update_price(ctx: &ReceiveContext, host: &mut Host<State>) -> Result<(), CustomContractError> {
-    let umbrella_feeds_contract = host.invoke_contract_read_only(
-        &UMBRELLA_REGISTRY_CONTRACT,
-        &String::from("UmbrellaFeeds"),
-        EntrypointName::new_unchecked("getAddress"),
-        Amount::zero(),
-    )?;
-
-    let umbrella_feeds_contract: ContractAddress =
-        if let Some(mut umbrella_feeds_contract) = umbrella_feeds_contract {
-            umbrella_feeds_contract.get()?
-        } else {
-            return Err(CustomContractError::InvokeContractError);
-        };
-
+fn update_price(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+) -> Result<PriceSource, CustomContractError> {
     let price_feed_name: String = ctx.parameter_cursor().get()?;
 
-    let price = host.invoke_contract_read_only(
-        &umbrella_feeds_contract,
-        &price_feed_name,
-        EntrypointName::new_unchecked("getPriceData"),
-        Amount::zero(),
-    )?;
-
-    let price_data: PriceData = if let Some(mut price) = price {
-        price.get()?
-    } else {
-        return Err(CustomContractError::InvokeContractError);
+    let now = ctx.metadata().block_time();
+
+    if let Ok(umbrella_feeds_contract) = resolve_umbrella_feeds(host, &host.state().registry) {
+        host.state_mut().umbrella_feeds = Some(umbrella_feeds_contract);
+    }
+
+    let state = host.state();
+
+    let sources = FallbackSources {
+        primary: state.umbrella_feeds,
+        secondary: state.secondary_umbrella_feeds,
+        last_known_good: state
+            .last_known_good
+            .get(&price_feed_name)
+            .map(|price_data| *price_data),
+        max_age: state.max_cache_age,
     };
 
-    // Check price feed is not outdated.
-    ensure!(
-        ctx.metadata().block_time()
-            < price_data
-                .timestamp
-                .checked_add(Duration::from_seconds(price_data.heartbeat))
-                .ok_or(CustomContractError::Overflow)?,
-        CustomContractError::PriceNotUpToDate
-    );
+    let SourcedPriceData { price_data, source } =
+        fetch_with_fallback(host, &sources, &price_feed_name, now)?;
 
-    host.state_mut()
-        .last_price_update
-        .insert(price_feed_name, price_data.price);
+    if source != PriceSource::LastKnownGood {
+        let _ = host
+            .state_mut()
+            .last_known_good
+            .insert(price_feed_name, price_data);
+    }
 
-    Ok(())
+    Ok(source)
 }
@@ -1,28 +1,95 @@
 //! Tests for the smart contract oracle integration.
 use concordium_smart_contract_testing::*;
 use concordium_std::{PublicKeyEd25519, SignatureEd25519};
+use oracle_integration::FallbackParams;
 use registry::ImportContractsParam;
+use staking_bank::InitParamsStakingBank;
+use umbrella_consumer::PriceSource;
 use umbrella_feeds::{InitParamsUmbrellaFeeds, Message, PriceData, UpdateParams};
 
 /// The tests accounts.
 const ALICE: AccountAddress = AccountAddress([0; 32]);
 const ALICE_ADDR: Address = Address::Account(ALICE);
+const BOB: AccountAddress = AccountAddress([1; 32]);
+const BOB_ADDR: Address = Address::Account(BOB);
 
 const ACC_INITIAL_BALANCE: Amount = Amount::from_ccd(10000);
 const SIGNER: Signer = Signer::with_one_key();
 
+// The validator registered in the `staking_bank` contract.
+// ATTENTION: Use a different key in production. This key and its private key is exposed and used for testing here.
+// Private key: 8ECA45107A878FB879B84401084B55AD4919FC0F7D14E8915D8A5989B1AE1C01
+const VALIDATOR: PublicKeyEd25519 = PublicKeyEd25519([
+    120, 154, 141, 6, 248, 239, 77, 224, 80, 62, 139, 136, 211, 204, 105, 208, 26, 11, 2, 208, 195,
+    253, 29, 192, 126, 199, 208, 39, 69, 4, 246, 32,
+]);
+
 const DUMMY_SIGNATURE: SignatureEd25519 = SignatureEd25519([
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
 ]);
 
+/// Returns the reject reason of a failed invoke if the contract rejected on its own.
+fn reject_reason(error: &ContractInvokeError) -> Option<i32> {
+    match &error.kind {
+        ContractInvokeErrorKind::ExecutionError {
+            failure_kind: InvokeFailure::ContractReject { code, .. },
+        } => Some(*code),
+        _ => None,
+    }
+}
+
 /// Test updating the price.
 #[test]
 fn test_updating_price() {
-    let (mut chain, umbrella_feeds_contract_address, integration_contract_address) =
-        initialize_chain_and_contract();
+    let (
+        mut chain,
+        _registry,
+        _staking_bank,
+        umbrella_feeds_contract_address,
+        integration_contract_address,
+    ) = initialize_chain_and_contract();
 
-    let feed_name: String = String::from("CCD-USD");
+    let price_data = PriceData {
+        data: 7,
+        heartbeat: 12,
+        timestamp: Timestamp::from_timestamp_millis(9),
+        price: 4,
+    };
+
+    update_umbrella_feeds_price(
+        &mut chain,
+        umbrella_feeds_contract_address,
+        String::from("CCD-USD"),
+        price_data,
+    );
+
+    // Updating the price data in the smart contract oracle integration.
+    update_integration_price(&mut chain, integration_contract_address, "CCD-USD")
+        .expect("Should be able to update the price in the integration contract");
+
+    assert_eq!(
+        get_prices(&chain, integration_contract_address),
+        vec![(String::from("CCD-USD"), 4)]
+    );
+
+    // Checking that the resolved `UmbrellaFeeds` contract address is cached.
+    assert_eq!(
+        get_umbrella_feeds(&chain, integration_contract_address),
+        Some(umbrella_feeds_contract_address)
+    );
+}
+
+/// Test that the cached `UmbrellaFeeds` contract address is refreshed when the registry entry changes.
+#[test]
+fn test_refreshing_umbrella_feeds() {
+    let (
+        mut chain,
+        registry,
+        staking_bank,
+        umbrella_feeds_contract_address,
+        integration_contract_address,
+    ) = initialize_chain_and_contract();
 
     let price_data = PriceData {
         data: 7,
@@ -31,10 +98,397 @@ fn test_updating_price() {
         price: 4,
     };
 
+    update_umbrella_feeds_price(
+        &mut chain,
+        umbrella_feeds_contract_address,
+        String::from("CCD-USD"),
+        price_data,
+    );
+
+    update_integration_price(&mut chain, integration_contract_address, "CCD-USD")
+        .expect("Should be able to update the price in the integration contract");
+
+    // Replacing the `UmbrellaFeeds` contract in the registry.
+
+    let module_reference = chain
+        .get_contract(umbrella_feeds_contract_address)
+        .expect("The umbrella_feeds contract should exist")
+        .module_reference;
+
+    let new_umbrella_feeds_contract_address =
+        initialize_umbrella_feeds(&mut chain, module_reference, registry, staking_bank);
+
+    let new_price_data = PriceData {
+        data: 7,
+        heartbeat: 12,
+        timestamp: Timestamp::from_timestamp_millis(10),
+        price: 5,
+    };
+
+    update_umbrella_feeds_price(
+        &mut chain,
+        new_umbrella_feeds_contract_address,
+        String::from("CCD-USD"),
+        new_price_data,
+    );
+
+    // The old contract still returns an up-to-date `CCD-USD` price, but the price is read from the new contract since
+    // the registry entry is looked up on every update.
+
+    update_integration_price(&mut chain, integration_contract_address, "CCD-USD")
+        .expect("Should be able to update the price from the new umbrella feeds contract");
+
+    assert_eq!(
+        get_umbrella_feeds(&chain, integration_contract_address),
+        Some(new_umbrella_feeds_contract_address)
+    );
+
+    assert_eq!(
+        get_prices(&chain, integration_contract_address),
+        vec![(String::from("CCD-USD"), 5)]
+    );
+
+    // Checking that a price feed that does not exist in the registered contract fails.
+
+    let update = update_integration_price(&mut chain, integration_contract_address, "BTC-USD")
+        .expect_err("Should fail to update a price feed that does not exist");
+
+    assert_eq!(reject_reason(&update), Some(-3));
+}
+
+/// Test updating the registry.
+#[test]
+fn test_set_registry() {
+    let (
+        mut chain,
+        _registry,
+        _staking_bank,
+        umbrella_feeds_contract_address,
+        integration_contract_address,
+    ) = initialize_chain_and_contract();
+
+    let new_registry = ContractAddress {
+        index: 100,
+        subindex: 0,
+    };
+
+    // Checking that only the owner can update the registry.
+
+    let update = set_registry(&mut chain, BOB, integration_contract_address, new_registry)
+        .expect_err("Should fail to update the registry without being the owner");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Checking that anyone can refresh the cached `UmbrellaFeeds` contract address.
+
+    chain
+        .contract_update(
+            SIGNER,
+            BOB,
+            BOB_ADDR,
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: integration_contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "smart_contract_oracle_integration.refreshUmbrellaFeeds".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to refresh the umbrella feeds contract address");
+
+    assert_eq!(
+        get_umbrella_feeds(&chain, integration_contract_address),
+        Some(umbrella_feeds_contract_address)
+    );
+
+    // Updating the registry clears the cached `UmbrellaFeeds` contract address.
+
+    set_registry(
+        &mut chain,
+        ALICE,
+        integration_contract_address,
+        new_registry,
+    )
+    .expect("Should be able to update the registry");
+
+    let invoke = chain
+        .contract_invoke(
+            ALICE,
+            ALICE_ADDR,
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "smart_contract_oracle_integration.registry".to_string(),
+                ),
+                address: integration_contract_address,
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Invoke registry");
+
+    let registry: ContractAddress = invoke.parse_return_value().expect("View return value");
+
+    assert_eq!(registry, new_registry);
+
+    assert_eq!(
+        get_umbrella_feeds(&chain, integration_contract_address),
+        None
+    );
+}
+
+/// Test falling back to the secondary `UmbrellaFeeds` contract and to the last known good price data.
+#[test]
+fn test_fallback_sources() {
+    let (
+        mut chain,
+        registry,
+        staking_bank,
+        umbrella_feeds_contract_address,
+        integration_contract_address,
+    ) = initialize_chain_and_contract();
+
+    update_umbrella_feeds_price(
+        &mut chain,
+        umbrella_feeds_contract_address,
+        String::from("CCD-USD"),
+        PriceData {
+            data: 7,
+            heartbeat: 12,
+            timestamp: Timestamp::from_timestamp_millis(9),
+            price: 4,
+        },
+    );
+
+    // The secondary `UmbrellaFeeds` contract is not registered in the registry.
+
+    let module_reference = chain
+        .get_contract(umbrella_feeds_contract_address)
+        .expect("The umbrella_feeds contract should exist")
+        .module_reference;
+
+    let secondary_umbrella_feeds_contract_address =
+        init_umbrella_feeds(&mut chain, module_reference, registry, staking_bank);
+
+    update_umbrella_feeds_price(
+        &mut chain,
+        secondary_umbrella_feeds_contract_address,
+        String::from("ETH-USD"),
+        PriceData {
+            data: 7,
+            heartbeat: 12,
+            timestamp: Timestamp::from_timestamp_millis(9),
+            price: 5,
+        },
+    );
+
+    let fallback = FallbackParams {
+        secondary_umbrella_feeds: Some(secondary_umbrella_feeds_contract_address),
+        max_cache_age: Duration::from_seconds(3600),
+    };
+
+    // Checking that only the owner can set the fallback sources.
+
+    let update = set_fallback(&mut chain, BOB, integration_contract_address, fallback)
+        .expect_err("Should fail to set the fallback sources without being the owner");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    set_fallback(&mut chain, ALICE, integration_contract_address, fallback)
+        .expect("Should be able to set the fallback sources");
+
+    let invoke = chain
+        .contract_invoke(
+            ALICE,
+            ALICE_ADDR,
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "smart_contract_oracle_integration.fallback".to_string(),
+                ),
+                address: integration_contract_address,
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Invoke fallback");
+
+    assert_eq!(invoke.parse_return_value::<FallbackParams>(), Ok(fallback));
+
+    // The price is read from the primary `UmbrellaFeeds` contract.
+
+    let update = update_integration_price(&mut chain, integration_contract_address, "CCD-USD")
+        .expect("Should be able to update the price from the primary contract");
+
+    assert_eq!(update.parse_return_value(), Ok(PriceSource::Primary));
+
+    // The primary contract does not have the `ETH-USD` price feed, so the price is read from the secondary contract.
+
+    let update = update_integration_price(&mut chain, integration_contract_address, "ETH-USD")
+        .expect("Should be able to update the price from the secondary contract");
+
+    assert_eq!(update.parse_return_value(), Ok(PriceSource::Secondary));
+
+    assert_eq!(
+        get_umbrella_feeds(&chain, integration_contract_address),
+        Some(umbrella_feeds_contract_address)
+    );
+
+    assert_eq!(
+        get_prices(&chain, integration_contract_address),
+        vec![(String::from("CCD-USD"), 4), (String::from("ETH-USD"), 5)]
+    );
+
+    // The heartbeat elapsed, so the last known good price data is used.
+
+    chain
+        .tick_block_time(Duration::from_seconds(60))
+        .expect("Should be able to advance the block time");
+
+    let update = update_integration_price(&mut chain, integration_contract_address, "CCD-USD")
+        .expect("Should be able to update the price from the last known good price data");
+
+    assert_eq!(update.parse_return_value(), Ok(PriceSource::LastKnownGood));
+
+    // The maximum age of the last known good price data elapsed, so the update fails.
+
+    chain
+        .tick_block_time(Duration::from_seconds(3600))
+        .expect("Should be able to advance the block time");
+
+    let update = update_integration_price(&mut chain, integration_contract_address, "CCD-USD")
+        .expect_err("Should fail to update the price without an available source");
+
+    assert_eq!(reject_reason(&update), Some(-2));
+}
+
+/// Invokes the `setRegistry` entry point of the integration contract.
+fn set_registry(
+    chain: &mut Chain,
+    sender: AccountAddress,
+    integration_contract_address: ContractAddress,
+    registry: ContractAddress,
+) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+    chain.contract_update(
+        SIGNER,
+        sender,
+        Address::Account(sender),
+        Energy::from(10000),
+        UpdateContractPayload {
+            amount: Amount::zero(),
+            address: integration_contract_address,
+            receive_name: OwnedReceiveName::new_unchecked(
+                "smart_contract_oracle_integration.setRegistry".to_string(),
+            ),
+            message: OwnedParameter::from_serial(&registry).expect("Serialize parameter"),
+        },
+    )
+}
+
+/// Invokes the `setFallback` entry point of the integration contract.
+fn set_fallback(
+    chain: &mut Chain,
+    sender: AccountAddress,
+    integration_contract_address: ContractAddress,
+    fallback: FallbackParams,
+) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+    chain.contract_update(
+        SIGNER,
+        sender,
+        Address::Account(sender),
+        Energy::from(10000),
+        UpdateContractPayload {
+            amount: Amount::zero(),
+            address: integration_contract_address,
+            receive_name: OwnedReceiveName::new_unchecked(
+                "smart_contract_oracle_integration.setFallback".to_string(),
+            ),
+            message: OwnedParameter::from_serial(&fallback).expect("Serialize parameter"),
+        },
+    )
+}
+
+/// Invokes the `update_price` entry point of the integration contract.
+fn update_integration_price(
+    chain: &mut Chain,
+    integration_contract_address: ContractAddress,
+    feed_name: &str,
+) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+    chain.contract_update(
+        SIGNER,
+        ALICE,
+        ALICE_ADDR,
+        Energy::from(10000),
+        UpdateContractPayload {
+            amount: Amount::zero(),
+            address: integration_contract_address,
+            receive_name: OwnedReceiveName::new_unchecked(
+                "smart_contract_oracle_integration.update_price".to_string(),
+            ),
+            message: OwnedParameter::from_serial(&String::from(feed_name))
+                .expect("Serialize parameter"),
+        },
+    )
+}
+
+/// Invokes the `prices` entry point of the integration contract.
+fn get_prices(chain: &Chain, integration_contract_address: ContractAddress) -> Vec<(String, u128)> {
+    let invoke = chain
+        .contract_invoke(
+            ALICE,
+            ALICE_ADDR,
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "smart_contract_oracle_integration.prices".to_string(),
+                ),
+                address: integration_contract_address,
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Invoke prices");
+
+    invoke.parse_return_value().expect("View return value")
+}
+
+/// Invokes the `umbrellaFeeds` entry point of the integration contract.
+fn get_umbrella_feeds(
+    chain: &Chain,
+    integration_contract_address: ContractAddress,
+) -> Option<ContractAddress> {
+    let invoke = chain
+        .contract_invoke(
+            ALICE,
+            ALICE_ADDR,
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "smart_contract_oracle_integration.umbrellaFeeds".to_string(),
+                ),
+                address: integration_contract_address,
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Invoke umbrellaFeeds");
+
+    invoke.parse_return_value().expect("View return value")
+}
+
+/// Signs and stores the price data of a price feed in the `umbrella_feeds` contract.
+fn update_umbrella_feeds_price(
+    chain: &mut Chain,
+    umbrella_feeds_contract_address: ContractAddress,
+    feed_name: String,
+    price_data: PriceData,
+) {
     use ed25519_dalek::{Signer, SigningKey};
 
     // The private key has to associated with the public key that is registered in
-    // the `staking_bank contract (development_constants.rs file)`.
+    // the `staking_bank` contract (`VALIDATOR`).
     // ATTENTION: Do not use this private key in production.
     let signing_key = SigningKey::from_bytes(&[
         142, 202, 69, 16, 122, 135, 143, 184, 121, 184, 68, 1, 8, 75, 85, 173, 73, 25, 252, 15,
@@ -100,82 +554,104 @@ fn test_updating_price() {
             },
         )
         .expect("Should be able to update the price in the umbrella oracle protocol");
+}
 
-    // Updating the price data in the smart contract oracle integration.
-    let _update = chain
-        .contract_update(
-            concordium_smart_contract_testing::Signer::with_one_key(),
+/// Initializes a new `umbrella_feeds` contract and imports it into the `registry` contract (replacing the previously
+/// registered `umbrella_feeds` contract).
+fn initialize_umbrella_feeds(
+    chain: &mut Chain,
+    module_reference: ModuleReference,
+    registry: ContractAddress,
+    staking_bank: ContractAddress,
+) -> ContractAddress {
+    let umbrella_feeds_contract_address =
+        init_umbrella_feeds(chain, module_reference, registry, staking_bank);
+
+    import_umbrella_feeds(chain, registry, umbrella_feeds_contract_address);
+
+    umbrella_feeds_contract_address
+}
+
+/// Initializes a new `umbrella_feeds` contract without importing it into the `registry` contract.
+fn init_umbrella_feeds(
+    chain: &mut Chain,
+    module_reference: ModuleReference,
+    registry: ContractAddress,
+    staking_bank: ContractAddress,
+) -> ContractAddress {
+    let input_parameter = InitParamsUmbrellaFeeds {
+        registry,
+        required_signatures: 1,
+        staking_bank,
+        decimals: 4,
+    };
+
+    let initialization_umbrella_feeds = chain
+        .contract_init(
+            Signer::with_one_key(),
             ALICE,
-            Address::Account(ALICE),
             Energy::from(10000),
-            UpdateContractPayload {
+            InitContractPayload {
                 amount: Amount::zero(),
-                address: integration_contract_address,
-                receive_name: OwnedReceiveName::new_unchecked(
-                    "smart_contract_oracle_integration.update_price".to_string(),
-                ),
-                message: OwnedParameter::from_serial(&String::from("CCD-USD"))
-                    .expect("Serialize parameter"),
+                mod_ref: module_reference,
+                init_name: OwnedContractName::new_unchecked("init_umbrella_feeds".to_string()),
+                param: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`InitContractsParam` should be a valid inut parameter"),
             },
         )
-        .expect("Should be able to update the price in the integration contract");
+        .expect("Initialization of `umbrella_feeds` should always succeed");
 
-    // Invoke the `prices` entry point and check that the prices were updated.
-    let invoke = chain
-        .contract_invoke(
+    initialization_umbrella_feeds.contract_address
+}
+
+/// Imports an `umbrella_feeds` contract into the `registry` contract (replacing the previously registered
+/// `umbrella_feeds` contract).
+fn import_umbrella_feeds(
+    chain: &mut Chain,
+    registry: ContractAddress,
+    umbrella_feeds_contract_address: ContractAddress,
+) {
+    let input_parameter = ImportContractsParam {
+        entries: vec![umbrella_feeds_contract_address],
+    };
+
+    // Importing the `umbrella_feeds` contract into the `registry` contract.
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
             ALICE,
-            ALICE_ADDR,
+            Address::Account(ALICE),
             Energy::from(10000),
             UpdateContractPayload {
-                amount: Amount::zero(),
-                receive_name: OwnedReceiveName::new_unchecked(
-                    "smart_contract_oracle_integration.prices".to_string(),
-                ),
-                address: integration_contract_address,
-                message: OwnedParameter::empty(),
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.importContracts".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
             },
         )
-        .expect("Invoke prices");
-
-    let rv: Vec<(String, u128)> = invoke.parse_return_value().expect("View return value");
-
-    assert_eq!(rv, vec![(String::from("CCD-USD"), 4)]);
+        .expect("Should be able to importContracts");
 }
 
 /// Setup the umbrella oracle protocol and the smart_contract_oracle_integration
 /// contract.
 ///
-/// Create an account for ALICE.
-fn initialize_chain_and_contract() -> (Chain, ContractAddress, ContractAddress) {
+/// Create accounts for ALICE and BOB. Returns the addresses of the registry, the staking bank, the umbrella feeds and
+/// the integration contracts.
+fn initialize_chain_and_contract() -> (
+    Chain,
+    ContractAddress,
+    ContractAddress,
+    ContractAddress,
+    ContractAddress,
+) {
     let mut chain = Chain::builder()
         .build()
         .expect("Expect setting up the chain");
 
-    // Create an account on the chain.
+    // Create accounts on the chain.
     chain.create_account(Account::new(ALICE, ACC_INITIAL_BALANCE));
-
-    // Load and deploy the module.
-    let module = module_load_v1("./oracle_integration.wasm.v1").expect("Module exists");
-    let deployment = chain
-        .module_deploy_v1(SIGNER, ALICE, module)
-        .expect("Deploy valid module");
-
-    // Initialize the oracle integration contract.
-    let init = chain
-        .contract_init(
-            SIGNER,
-            ALICE,
-            Energy::from(10000),
-            InitContractPayload {
-                amount: Amount::zero(),
-                mod_ref: deployment.module_reference,
-                init_name: OwnedContractName::new_unchecked(
-                    "init_smart_contract_oracle_integration".to_string(),
-                ),
-                param: OwnedParameter::empty(),
-            },
-        )
-        .expect("Initialize integration contract");
+    chain.create_account(Account::new(BOB, ACC_INITIAL_BALANCE));
 
     // Load and deploy the Umbrella oracle protocol.
 
@@ -213,6 +689,13 @@ fn initialize_chain_and_contract() -> (Chain, ContractAddress, ContractAddress)
         )
         .expect("`staking_bank.wasm.v1` deployment should always succeed");
 
+    let input_parameter = InitParamsStakingBank {
+        validators: vec![(VALIDATOR, String::from("https://validator.dev.umb.network"))],
+        registry: initialization_registry.contract_address,
+        liveness_window: Duration::from_seconds(3600),
+        unbonding_period: Duration::from_seconds(86400),
+    };
+
     let initialization_staking_bank = chain
         .contract_init(
             Signer::with_one_key(),
@@ -222,7 +705,8 @@ fn initialize_chain_and_contract() -> (Chain, ContractAddress, ContractAddress)
                 amount: Amount::zero(),
                 mod_ref: deployment_staking_bank.module_reference,
                 init_name: OwnedContractName::new_unchecked("init_staking_bank".to_string()),
-                param: OwnedParameter::empty(),
+                param: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`InitParamsStakingBank` should be a valid inut parameter"),
             },
         )
         .expect("Initialization of `staking_bank` should always succeed");
@@ -237,52 +721,42 @@ fn initialize_chain_and_contract() -> (Chain, ContractAddress, ContractAddress)
         )
         .expect("`Umbrella_feeds.wasm.v1` deployment should always succeed");
 
-    let input_parameter = InitParamsUmbrellaFeeds {
-        registry: initialization_registry.contract_address,
-        required_signatures: 1,
-        staking_bank: initialization_staking_bank.contract_address,
-        decimals: 4,
-    };
+    let umbrella_feeds_contract_address = initialize_umbrella_feeds(
+        &mut chain,
+        deployment.module_reference,
+        initialization_registry.contract_address,
+        initialization_staking_bank.contract_address,
+    );
 
-    let initialization_umbrella_feeds = chain
+    // Load and deploy the module.
+    let module = module_load_v1("./oracle_integration.wasm.v1").expect("Module exists");
+    let deployment = chain
+        .module_deploy_v1(SIGNER, ALICE, module)
+        .expect("Deploy valid module");
+
+    // Initialize the oracle integration contract with the registry contract address.
+    let init = chain
         .contract_init(
-            Signer::with_one_key(),
+            SIGNER,
             ALICE,
             Energy::from(10000),
             InitContractPayload {
                 amount: Amount::zero(),
                 mod_ref: deployment.module_reference,
-                init_name: OwnedContractName::new_unchecked("init_umbrella_feeds".to_string()),
-                param: OwnedParameter::from_serial(&input_parameter)
-                    .expect("`InitContractsParam` should be a valid inut parameter"),
-            },
-        )
-        .expect("Initialization of `umbrella_feeds` should always succeed");
-
-    let input_parameter = ImportContractsParam {
-        entries: vec![initialization_umbrella_feeds.contract_address],
-    };
-
-    // Importing the `umbrella_feeds` contract into the `registry` contract.
-    let _update = chain
-        .contract_update(
-            Signer::with_one_key(),
-            ALICE,
-            Address::Account(ALICE),
-            Energy::from(10000),
-            UpdateContractPayload {
-                address: initialization_registry.contract_address,
-                receive_name: OwnedReceiveName::new_unchecked("registry.importContracts".into()),
-                message: OwnedParameter::from_serial(&input_parameter)
-                    .expect("`input_parameter` should be a valid inut parameter"),
-                amount: Amount::from_ccd(0),
+                init_name: OwnedContractName::new_unchecked(
+                    "init_smart_contract_oracle_integration".to_string(),
+                ),
+                param: OwnedParameter::from_serial(&initialization_registry.contract_address)
+                    .expect("Serialize parameter"),
             },
         )
-        .expect("Should be able to importContracts");
+        .expect("Initialize integration contract");
 
     (
         chain,
-        initialization_umbrella_feeds.contract_address,
+        initialization_registry.contract_address,
+        initialization_staking_bank.contract_address,
+        umbrella_feeds_contract_address,
         init.contract_address,
     )
 }
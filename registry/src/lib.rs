@@ -3,12 +3,38 @@
 //! # Contracts Registry
 //!
 //! The protocol uses this registry to fetch current contract addresses.
-//! This contract has an owner.
-//! The owner can:
-//! - Register contracts into this registry with the `importAddresses` and the `importContracts` entry points.
-//! - Natively upgrade the `UmbrellaFeeds` contract via this registry contract by invoking the `atomicUpdate` entry point.
-//! - Override contract addresses registered (e.g. in case they don't have the entry points `upgradeNatively` implemented) by invoking the `importAddresses` and the `importContracts` entry points.
+//! This contract has an owner and role-based access control. The owner has all roles except `Guardian`.
+//! - An address with the `Importer` role can register contracts into this registry with the `importAddresses` and the `importContracts` entry points and remove them with `removeAddresses`.
+//! - An address with the `Upgrader` role can natively upgrade the `UmbrellaFeeds` contract via this registry contract by invoking the `atomicUpdate` entry point (or several contracts at once with `atomicUpdateMany`) and upgrade this registry with `upgrade`.
+//! - An address with the `Importer` role can override contract addresses registered (e.g. in case they don't have the entry points `upgradeNatively` implemented) by invoking the `importAddresses` and the `importContracts` entry points.
+//! - An address with the `Admin` role can grant and revoke roles (`grantRole`, `revokeRole`).
+//! - An address with the `Guardian` role can revoke the `Upgrader` and `Importer` roles (e.g. if a key got compromised).
+//!
+//! An admin can configure an M-of-N multisig (`setMultisig`). A signer proposes an operation (`importAddresses`,
+//! `importContracts`, `removeAddresses`, `atomicUpdate`, `atomicUpdateMany`, `rollback`, `upgrade`,
+//! `transferOwnership`, `setMultisig`, `grantRole`, `revokeRole` or `setAllowlist`) with `propose`, the other signers
+//! approve it with `approve`, and the operation is executed once the threshold of approvals is reached. Proposals expire
+//! after the proposal duration. Once a multisig is configured, the owner loses its implicit roles and can neither
+//! transfer nor renounce the ownership nor change the multisig on its own.
+//!
+//! An admin can set a minimum delay (`setMinDelay`) for the `importAddresses`, `importContracts`, `removeAddresses`,
+//! `atomicUpdate`, `atomicUpdateMany`, `rollback`, `upgrade`, `grantRole`, `revokeRole`, `setAllowlist` and
+//! `setMinDelay` operations. Once it is set, these operations are queued with `queue`, can be cancelled with `cancel`
+//! and can be executed with `execute` after the delay (approved multisig proposals are queued as well). The delay can be
+//! raised directly. Only a guardian can bypass the delay in an emergency with `emergencyExecute`, except for the
+//! operations of admins. The `Guardian` role can not be granted to oneself. Executing the `UpgradeRegistry` operation
+//! only approves the upgrade, an upgrader then invokes `upgrade` with the approved parameters.
+//!
+//! An admin can pin a key name to allowlisted module references and a contract name (`setAllowlist`). Contracts of other
+//! code are then rejected when they are registered under this name.
+//!
+//! The registry records the module a contract ran before each native upgrade. An upgrader can restore it with `rollback`.
+//!
 //! ATTENTION: If you want to upgrade the `UmbrellaFeeds` contract, use the `atomicUpdate` function to natively upgrade the `UmbrellaFeeds` contract.
+//!
+//! The ownership is transferred in two steps: the owner proposes a new owner with `transferOwnership` and the ownership is
+//! only transferred once the new owner invokes `acceptOwnership`. Until then, the owner can cancel the transfer with
+//! `cancelOwnershipTransfer`.
 use concordium_std::*;
 use core::fmt::Debug;
 
@@ -17,8 +43,64 @@ use core::fmt::Debug;
 struct State<S: HasStateApi> {
     // The owner of this contract. It can register/override/atomically upgrade contract addresses in this registry.
     owner: Option<Address>,
+    // The address the ownership is transferred to once it invokes `acceptOwnership`.
+    pending_owner: Option<Address>,
+    // The roles granted to addresses (in addition to the owner that has all roles except `Guardian`).
+    roles: StateSet<(Address, Role), S>,
+    // The signers of the multisig that can propose and approve operations.
+    signers: Vec<Address>,
+    // The number of approvals of signers needed to execute a proposal.
+    threshold: u8,
+    // The duration a proposal can be approved before it expires.
+    proposal_duration: Duration,
+    // The id of the next proposal.
+    next_proposal_id: u64,
+    // Mapping from the proposal id to the proposals that have not been executed yet.
+    proposals: StateMap<u64, Proposal, S>,
+    // The delay between queueing and executing a timelocked operation.
+    min_delay: Duration,
+    // The id of the next queued operation.
+    next_operation_id: u64,
+    // Mapping from the operation id to the queued operations that have not been executed or cancelled yet.
+    queued_operations: StateMap<u64, QueuedOperation, S>,
     // Mapping from key to contract address. The key/name of a contract is its string name.
     registry: StateMap<String, ContractAddress, S>,
+    // Mapping from key to all contract addresses registered under (or removed from) the key (append-only, oldest first).
+    history: StateMap<String, Vec<HistoryEntry>, S>,
+    // Mapping from key to the code that is allowed to be registered under the key (keys without an allowlist accept any
+    // contract).
+    allowlist: StateMap<String, Allowlist, S>,
+    // Mapping from contract address to the module references the contract ran before its native upgrades via this
+    // registry (oldest first). The last module is restored by `rollback`.
+    previous_modules: StateMap<ContractAddress, Vec<ModuleReference>, S>,
+    // The upgrade of this registry approved by an executed `UpgradeRegistry` operation (consumed by `upgrade`).
+    approved_upgrade: Option<UpgradeParams>,
+}
+
+/// A contract address registered under (or removed from) a key/name at some point in time.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq, Clone)]
+pub struct HistoryEntry {
+    /// The registered contract address (`None` if the key/name got removed).
+    pub destination: Option<ContractAddress>,
+    /// The block time of the registration or removal.
+    pub timestamp: Timestamp,
+    /// The address that registered or removed the contract address (the proposer of multisig proposals and the address
+    /// that queued timelocked operations).
+    pub sender: Address,
+}
+
+/// The roles of the access control of this contract.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq, Clone, Copy)]
+pub enum Role {
+    /// Can grant and revoke roles.
+    Admin,
+    /// Can natively upgrade contracts via `atomicUpdate` and `atomicUpdateMany` and this registry via `upgrade`.
+    Upgrader,
+    /// Can register contracts via `importAddresses` and `importContracts` and remove them via `removeAddresses`.
+    Importer,
+    /// Can revoke the `Upgrader` and `Importer` roles, cancel queued operations and bypass the timelock of the operations
+    /// of importers and upgraders.
+    Guardian,
 }
 
 /// All smart contract errors.
@@ -39,6 +121,48 @@ enum CustomContractError {
     InvokeContractError, // -6
     /// Failed because this contract has no owner anymore (ownership was renounced).
     NoOwner, // -7
+    /// Failed because there is no pending ownership transfer.
+    NoPendingOwner, // -8
+    /// Failed because the proposal does not exist (or was already executed).
+    ProposalNotFound, // -9
+    /// Failed because the proposal expired.
+    ProposalExpired, // -10
+    /// Failed because the signer already approved the proposal.
+    AlreadyApproved, // -11
+    /// Failed because the threshold is zero or greater than the number of signers.
+    InvalidThreshold, // -12
+    /// Failed because a signer is provided more than once.
+    DuplicateSigner, // -13
+    /// Failed because the operation can not be timelocked.
+    InvalidOperation, // -14
+    /// Failed because the queued operation does not exist (or was already executed/cancelled).
+    OperationNotFound, // -15
+    /// Failed because the minimum delay of the queued operation has not passed yet.
+    TimelockNotReady, // -16
+    /// Failed because the operation has to be queued (a minimum delay is set).
+    TimelockActive, // -17
+    /// Failed because the module of the contract is not in the allowlist of the key/name.
+    UnapprovedModule, // -18
+    /// Failed because the contract name of the contract does not match the allowlist of the key/name.
+    UnapprovedContractName, // -19
+    /// Upgrade failed because the new module does not exist.
+    FailedUpgradeMissingModule, // -20
+    /// Upgrade failed because the new module does not contain a contract with a
+    /// matching name.
+    FailedUpgradeMissingContract, // -21
+    /// Upgrade failed because the smart contract version of the module is not
+    /// supported.
+    FailedUpgradeUnsupportedModuleVersion, // -22
+    /// Failed because the contract was not natively upgraded via this registry (or all upgrades were rolled back).
+    NoPreviousModule, // -23
+    /// Failed because the entry point is a hook that the registered contracts only accept from this registry.
+    ForwardDenied, // -24
+    /// Failed because the multisig has more than 255 signers.
+    TooManySigners, // -25
+    /// Failed because the proposal has not expired yet.
+    ProposalNotExpired, // -26
+    /// Failed because a multisig is configured (the operation has to be proposed to the multisig).
+    MultisigActive, // -27
 }
 
 /// Mapping errors related to logging to CustomContractError.
@@ -58,6 +182,18 @@ impl<T> From<CallContractError<T>> for CustomContractError {
     }
 }
 
+/// Mapping errors related to contract upgrades to CustomContractError.
+impl From<UpgradeError> for CustomContractError {
+    #[inline(always)]
+    fn from(ue: UpgradeError) -> Self {
+        match ue {
+            UpgradeError::MissingModule => Self::FailedUpgradeMissingModule,
+            UpgradeError::MissingContract => Self::FailedUpgradeMissingContract,
+            UpgradeError::UnsupportedModuleVersion => Self::FailedUpgradeUnsupportedModuleVersion,
+        }
+    }
+}
+
 /// Tagged events to be serialized for the event log.
 #[derive(Debug, Serial, SchemaType)]
 #[concordium(repr(u8))]
@@ -68,6 +204,57 @@ enum Event {
     /// The event tracks whenever the contract ownership gets transferred.
     #[concordium(tag = 1)]
     OwnershipTransferred(OwnershipTransferredEvent),
+    /// The event tracks whenever a contract ownership transfer gets started or cancelled.
+    #[concordium(tag = 2)]
+    OwnershipTransferPending(OwnershipTransferPendingEvent),
+    /// The event tracks whenever a role gets granted to an address.
+    #[concordium(tag = 3)]
+    RoleGranted(RoleGrantedEvent),
+    /// The event tracks whenever a role gets revoked from an address.
+    #[concordium(tag = 4)]
+    RoleRevoked(RoleRevokedEvent),
+    /// The event tracks whenever the multisig configuration gets updated.
+    #[concordium(tag = 5)]
+    MultisigUpdated(MultisigUpdatedEvent),
+    /// The event tracks whenever an operation gets proposed.
+    #[concordium(tag = 6)]
+    ProposalCreated(ProposalCreatedEvent),
+    /// The event tracks whenever a proposal gets approved by a signer.
+    #[concordium(tag = 7)]
+    ProposalApproved(ProposalApprovedEvent),
+    /// The event tracks whenever a proposal gets executed.
+    #[concordium(tag = 8)]
+    ProposalExecuted(ProposalExecutedEvent),
+    /// The event tracks whenever the minimum delay of the timelock gets updated.
+    #[concordium(tag = 9)]
+    MinDelayUpdated(MinDelayUpdatedEvent),
+    /// The event tracks whenever an operation gets queued.
+    #[concordium(tag = 10)]
+    OperationQueued(OperationQueuedEvent),
+    /// The event tracks whenever a queued operation gets executed.
+    #[concordium(tag = 11)]
+    OperationExecuted(OperationExecutedEvent),
+    /// The event tracks whenever a queued operation gets cancelled.
+    #[concordium(tag = 12)]
+    OperationCancelled(OperationCancelledEvent),
+    /// The event tracks whenever a guardian executes an operation bypassing the timelock.
+    #[concordium(tag = 13)]
+    EmergencyExecuted(EmergencyExecutedEvent),
+    /// The event tracks whenever a contract address gets removed from this registry.
+    #[concordium(tag = 14)]
+    LogUnregistered(LogUnregisteredEvent),
+    /// The event tracks whenever the allowlist of a key/name gets updated or removed.
+    #[concordium(tag = 15)]
+    AllowlistUpdated(AllowlistUpdatedEvent),
+    /// The event tracks whenever a contract gets natively upgraded via this registry.
+    #[concordium(tag = 16)]
+    ContractUpgraded(ContractUpgradedEvent),
+    /// The event tracks whenever a registered contract address gets replaced by another contract address.
+    #[concordium(tag = 17)]
+    ContractReplaced(ContractReplacedEvent),
+    /// The event tracks whenever an expired proposal gets removed.
+    #[concordium(tag = 18)]
+    ProposalRemoved(ProposalRemovedEvent),
 }
 
 /// The LogRegisteredEvent is logged when a new contract address gets registered/atomically upgraded in this registry (potentially replacing an old contract address).
@@ -77,6 +264,54 @@ pub struct LogRegisteredEvent {
     pub destination: ContractAddress,
     /// The key/name of a contract.
     pub name: String,
+    /// The contract address that was registered under the key/name before (if any).
+    pub previous: Option<ContractAddress>,
+}
+
+/// The ContractUpgradedEvent is logged when a contract gets natively upgraded via the `atomicUpdate` or the
+/// `atomicUpdateMany` entry points.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ContractUpgradedEvent {
+    /// The contract address that got upgraded.
+    pub contract_address: ContractAddress,
+    /// The key/name of the contract.
+    pub name: String,
+    /// The module reference of the contract before the upgrade.
+    pub old_module: ModuleReference,
+    /// The module reference of the contract after the upgrade.
+    pub new_module: ModuleReference,
+    /// The migration entry point that was invoked after the upgrade (if any).
+    pub migrate: Option<OwnedEntrypointName>,
+}
+
+/// The ContractReplacedEvent is logged when the contract address registered under a key/name gets replaced by another
+/// contract address.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ContractReplacedEvent {
+    /// The key/name of the contract.
+    pub name: String,
+    /// The contract address that was registered before.
+    pub old_address: ContractAddress,
+    /// The contract address that is registered now.
+    pub new_address: ContractAddress,
+}
+
+/// The AllowlistUpdatedEvent is logged when the allowlist of a key/name gets updated or removed.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct AllowlistUpdatedEvent {
+    /// The key/name of a contract.
+    pub name: String,
+    /// The new allowlist (`None` if the allowlist got removed).
+    pub allowlist: Option<Allowlist>,
+}
+
+/// The LogUnregisteredEvent is logged when a contract address gets removed from this registry.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct LogUnregisteredEvent {
+    /// The contract address that got removed.
+    pub destination: ContractAddress,
+    /// The key/name of a contract.
+    pub name: String,
 }
 
 /// The OwnershipTransferredEvent is logged when the contract ownership gets transferred.
@@ -88,6 +323,183 @@ pub struct OwnershipTransferredEvent {
     pub new_owner: Option<Address>,
 }
 
+/// The OwnershipTransferPendingEvent is logged when the contract ownership transfer gets started or cancelled.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct OwnershipTransferPendingEvent {
+    /// The current owner's address.
+    pub owner: Option<Address>,
+    /// The pending owner's address (`None` if the ownership transfer got cancelled).
+    pub pending_owner: Option<Address>,
+}
+
+/// The RoleGrantedEvent is logged when a role gets granted to an address.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct RoleGrantedEvent {
+    /// The address the role got granted to.
+    pub address: Address,
+    /// The role.
+    pub role: Role,
+    /// The address that granted the role.
+    pub sender: Address,
+}
+
+/// The RoleRevokedEvent is logged when a role gets revoked from an address.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct RoleRevokedEvent {
+    /// The address the role got revoked from.
+    pub address: Address,
+    /// The role.
+    pub role: Role,
+    /// The address that revoked the role.
+    pub sender: Address,
+}
+
+/// The MultisigUpdatedEvent is logged when the multisig configuration gets updated.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct MultisigUpdatedEvent {
+    /// The signers of the multisig.
+    pub signers: Vec<Address>,
+    /// The number of approvals needed to execute a proposal.
+    pub threshold: u8,
+    /// The duration a proposal can be approved before it expires.
+    pub proposal_duration: Duration,
+}
+
+/// The ProposalCreatedEvent is logged when an operation gets proposed.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ProposalCreatedEvent {
+    /// The id of the proposal.
+    pub proposal_id: u64,
+    /// The signer that proposed the operation.
+    pub proposer: Address,
+    /// The time after which the proposal can not be approved anymore.
+    pub expiry: Timestamp,
+}
+
+/// The ProposalApprovedEvent is logged when a proposal gets approved by a signer (including the proposer).
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ProposalApprovedEvent {
+    /// The id of the proposal.
+    pub proposal_id: u64,
+    /// The signer that approved the proposal.
+    pub signer: Address,
+    /// The number of approvals of current signers.
+    pub approvals: u8,
+}
+
+/// The ProposalExecutedEvent is logged when a proposal gets executed.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ProposalExecutedEvent {
+    /// The id of the proposal.
+    pub proposal_id: u64,
+}
+
+/// The ProposalRemovedEvent is logged when an expired proposal gets removed.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ProposalRemovedEvent {
+    /// The id of the proposal.
+    pub proposal_id: u64,
+}
+
+/// The MinDelayUpdatedEvent is logged when the minimum delay of the timelock gets updated.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct MinDelayUpdatedEvent {
+    /// The new minimum delay.
+    pub min_delay: Duration,
+}
+
+/// The OperationQueuedEvent is logged when an operation gets queued.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct OperationQueuedEvent {
+    /// The id of the queued operation.
+    pub operation_id: u64,
+    /// The address that queued the operation (the proposer for multisig proposals).
+    pub sender: Address,
+    /// The time after which the operation can be executed.
+    pub eta: Timestamp,
+}
+
+/// The OperationExecutedEvent is logged when a queued operation gets executed.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct OperationExecutedEvent {
+    /// The id of the queued operation.
+    pub operation_id: u64,
+}
+
+/// The OperationCancelledEvent is logged when a queued operation gets cancelled.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct OperationCancelledEvent {
+    /// The id of the queued operation.
+    pub operation_id: u64,
+    /// The address that cancelled the operation.
+    pub sender: Address,
+}
+
+/// The EmergencyExecutedEvent is logged when a guardian executes an operation bypassing the timelock.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct EmergencyExecutedEvent {
+    /// The guardian that executed the operation.
+    pub sender: Address,
+}
+
+impl<S: HasStateApi> State<S> {
+    /// Internal function that returns a boolean if the address has been granted the role or is the owner. The owner has
+    /// all roles except `Guardian`, so that it can not bypass the timelock on its own. Once a multisig is configured, the
+    /// owner only has the roles granted to it.
+    fn has_role(&self, address: &Address, role: Role) -> bool {
+        (role != Role::Guardian && self.signers.is_empty() && self.owner == Some(*address))
+            || self.roles.contains(&(*address, role))
+    }
+
+    /// Internal function that registers the contract address under the key/name, appends it to the history of the
+    /// key/name and returns the previously registered contract address.
+    fn register(
+        &mut self,
+        name: String,
+        destination: ContractAddress,
+        sender: Address,
+        now: Timestamp,
+    ) -> Option<ContractAddress> {
+        let previous = self.registry.insert(name.clone(), destination);
+
+        self.record_history(name, Some(destination), sender, now);
+
+        previous
+    }
+
+    /// Internal function that removes the key/name, appends the removal to the history of the key/name and returns the
+    /// removed contract address.
+    fn unregister(
+        &mut self,
+        name: String,
+        sender: Address,
+        now: Timestamp,
+    ) -> Option<ContractAddress> {
+        let removed = self.registry.remove_and_get(&name)?;
+
+        self.record_history(name, None, sender, now);
+
+        Some(removed)
+    }
+
+    /// Internal function that appends an entry to the history of the key/name.
+    fn record_history(
+        &mut self,
+        name: String,
+        destination: Option<ContractAddress>,
+        sender: Address,
+        now: Timestamp,
+    ) {
+        self.history.entry(name).or_default().modify(|history| {
+            history.push(HistoryEntry {
+                destination,
+                timestamp: now,
+                sender,
+            })
+        });
+    }
+}
+
 /// The init function that creates a new registry smart contract.
 #[init(contract = "registry", event = "Event", enable_logger)]
 fn init<S: HasStateApi>(
@@ -106,11 +518,25 @@ fn init<S: HasStateApi>(
     Ok(State {
         registry: state_builder.new_map(),
         owner: Some(owner),
+        pending_owner: None,
+        roles: state_builder.new_set(),
+        signers: Vec::new(),
+        threshold: 0,
+        proposal_duration: Duration::from_millis(0),
+        next_proposal_id: 0,
+        proposals: state_builder.new_map(),
+        history: state_builder.new_map(),
+        allowlist: state_builder.new_map(),
+        previous_modules: state_builder.new_map(),
+        min_delay: Duration::from_millis(0),
+        next_operation_id: 0,
+        queued_operations: state_builder.new_map(),
+        approved_upgrade: None,
     })
 }
 
 /// Part of the parameter type for the contract function `importAddresses`.
-#[derive(Serialize, SchemaType)]
+#[derive(Serialize, SchemaType, Clone)]
 pub struct ImportAddressesParam {
     /// The key/name of a contract.
     pub name: String,
@@ -119,7 +545,7 @@ pub struct ImportAddressesParam {
 }
 
 /// The parameter type for the contract function `importAddresses`.
-#[derive(Serialize, SchemaType)]
+#[derive(Serialize, SchemaType, Clone)]
 #[concordium(transparent)]
 pub struct ImportAddressesParams {
     /// List of ImportAddressParam.
@@ -127,7 +553,8 @@ pub struct ImportAddressesParams {
     pub entries: Vec<ImportAddressesParam>,
 }
 
-/// The owner can import new contract addresses and override old addresses (if they exist under the provided name) by providing the new contract address and its key/name.
+/// An importer can import new contract addresses and override old addresses (if they exist under the provided name) by providing the new contract address and its key/name.
+/// If a minimum delay is set, the operation has to be queued instead.
 /// This entry point can be used for contracts that for some reason do not have the `getName` entry point.
 /// ATTENTION: If you want to upgrade the `UmbrellaFeeds` contract, use the `atomicUpdate` function to natively upgrade the `UmbrellaFeeds` contract.
 #[receive(
@@ -143,31 +570,75 @@ fn import_addresses<S: HasStateApi>(
     host: &mut impl HasHost<State<S>, StateApiType = S>,
     logger: &mut impl HasLogger,
 ) -> Result<(), CustomContractError> {
-    ensure_eq!(
-        ctx.sender(),
-        host.state().owner.ok_or(CustomContractError::NoOwner)?,
+    ensure!(
+        host.state().has_role(&ctx.sender(), Role::Importer),
         CustomContractError::UnauthorizedAccount
     );
 
+    ensure!(
+        host.state().min_delay.millis() == 0,
+        CustomContractError::TimelockActive
+    );
+
     let import_contracts: ImportAddressesParams = ctx.parameter_cursor().get()?;
 
-    for entry in import_contracts.entries {
-        host.state_mut()
-            .registry
-            .insert(entry.name.clone(), entry.destination);
+    register_addresses(
+        host,
+        logger,
+        import_contracts,
+        ctx.sender(),
+        ctx.metadata().slot_time(),
+    )
+}
+
+/// Internal function that logs the `LogRegistered` event and, if another contract address was registered under the
+/// key/name before, the `ContractReplaced` event.
+fn log_registered(
+    logger: &mut impl HasLogger,
+    name: String,
+    destination: ContractAddress,
+    previous: Option<ContractAddress>,
+) -> Result<(), CustomContractError> {
+    logger.log(&Event::LogRegistered(LogRegisteredEvent {
+        name: name.clone(),
+        destination,
+        previous,
+    }))?;
 
-        // Log LogRegistered event
-        logger.log(&Event::LogRegistered(LogRegisteredEvent {
-            name: entry.name,
-            destination: entry.destination,
+    if let Some(old_address) = previous.filter(|old_address| *old_address != destination) {
+        logger.log(&Event::ContractReplaced(ContractReplacedEvent {
+            name,
+            old_address,
+            new_address: destination,
         }))?;
     }
 
     Ok(())
 }
 
+/// Internal function that registers the contract addresses under the provided keys/names.
+fn register_addresses<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    import_contracts: ImportAddressesParams,
+    sender: Address,
+    now: Timestamp,
+) -> Result<(), CustomContractError> {
+    for entry in import_contracts.entries {
+        ensure_approved(host, &entry.name, entry.destination)?;
+
+        let previous =
+            host.state_mut()
+                .register(entry.name.clone(), entry.destination, sender, now);
+
+        log_registered(logger, entry.name, entry.destination, previous)?;
+    }
+
+    Ok(())
+}
+
 /// The parameter type for the contract function `importContracts`.
-#[derive(Serialize, SchemaType)]
+#[derive(Serialize, SchemaType, Clone)]
 #[concordium(transparent)]
 pub struct ImportContractsParam {
     /// List of contract addresses.
@@ -175,7 +646,8 @@ pub struct ImportContractsParam {
     pub entries: Vec<ContractAddress>,
 }
 
-/// The owner can import new contract addresses and override old addresses (if they exist under the provided name) by providing the new contract address. The key/name of the contract is queried from the provided contract address.
+/// An importer can import new contract addresses and override old addresses (if they exist under the provided name) by providing the new contract address. The key/name of the contract is queried from the provided contract address.
+/// If a minimum delay is set, the operation has to be queued instead.
 /// ATTENTION: If you want to upgrade the `UmbrellaFeeds` contract, use the `atomicUpdate` function to natively upgrade the `UmbrellaFeeds` contract.
 #[receive(
     contract = "registry",
@@ -190,14 +662,35 @@ fn import_contracts<S: HasStateApi>(
     host: &mut impl HasHost<State<S>, StateApiType = S>,
     logger: &mut impl HasLogger,
 ) -> Result<(), CustomContractError> {
-    ensure_eq!(
-        ctx.sender(),
-        host.state().owner.ok_or(CustomContractError::NoOwner)?,
+    ensure!(
+        host.state().has_role(&ctx.sender(), Role::Importer),
         CustomContractError::UnauthorizedAccount
     );
 
+    ensure!(
+        host.state().min_delay.millis() == 0,
+        CustomContractError::TimelockActive
+    );
+
     let import_contracts: ImportContractsParam = ctx.parameter_cursor().get()?;
 
+    register_contracts(
+        host,
+        logger,
+        import_contracts,
+        ctx.sender(),
+        ctx.metadata().slot_time(),
+    )
+}
+
+/// Internal function that registers the contract addresses under the keys/names queried from the contracts.
+fn register_contracts<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    import_contracts: ImportContractsParam,
+    sender: Address,
+    now: Timestamp,
+) -> Result<(), CustomContractError> {
     for contract_address in import_contracts.entries {
         let name = host.invoke_contract_read_only(
             &contract_address,
@@ -210,22 +703,20 @@ fn import_contracts<S: HasStateApi>(
             .ok_or(CustomContractError::InvokeContractError)?
             .get()?;
 
-        host.state_mut()
-            .registry
-            .insert(name.clone(), contract_address);
+        ensure_approved(host, &name, contract_address)?;
 
-        // Log LogRegistered event
-        logger.log(&Event::LogRegistered(LogRegisteredEvent {
-            name,
-            destination: contract_address,
-        }))?;
+        let previous = host
+            .state_mut()
+            .register(name.clone(), contract_address, sender, now);
+
+        log_registered(logger, name, contract_address, previous)?;
     }
 
     Ok(())
 }
 
 /// The parameter type for the contract function `atomicUpdate`.
-#[derive(Serialize, SchemaType)]
+#[derive(Serialize, SchemaType, Clone)]
 pub struct AtomicUpdateParam {
     /// The new module reference.
     pub module: ModuleReference,
@@ -235,8 +726,9 @@ pub struct AtomicUpdateParam {
     pub contract_address: ContractAddress,
 }
 
-/// The parameter type for the contract function `upgradeNatively`.
-#[derive(Debug, Serialize, SchemaType)]
+/// The parameter type for the contract function `upgradeNatively` (invoked on the upgraded contracts) and the contract
+/// function `upgrade` of this contract.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq, Clone)]
 pub struct UpgradeParams {
     /// The new module reference.
     pub module: ModuleReference,
@@ -244,7 +736,8 @@ pub struct UpgradeParams {
     pub migrate: Option<(OwnedEntrypointName, OwnedParameter)>,
 }
 
-/// This entry point ensures, that the old and the new contracts are aware of their states in the registry by calling the `upgradeNatively` and the `unregister` hooks.
+/// An upgrader can natively upgrade a contract. This entry point ensures, that the old and the new contracts are aware of their states in the registry by calling the `upgradeNatively` and the `unregister` hooks.
+/// If a minimum delay is set, the operation has to be queued instead.
 /// ATTENTION: If you want to upgrade the `UmbrellaFeeds` contract, use this function to natively upgrade the `UmbrellaFeeds` contract.
 #[receive(
     contract = "registry",
@@ -259,14 +752,133 @@ fn atomic_update<S: HasStateApi>(
     host: &mut impl HasHost<State<S>, StateApiType = S>,
     logger: &mut impl HasLogger,
 ) -> Result<(), CustomContractError> {
-    ensure_eq!(
-        ctx.sender(),
-        host.state().owner.ok_or(CustomContractError::NoOwner)?,
+    ensure!(
+        host.state().has_role(&ctx.sender(), Role::Upgrader),
         CustomContractError::UnauthorizedAccount
     );
 
+    ensure!(
+        host.state().min_delay.millis() == 0,
+        CustomContractError::TimelockActive
+    );
+
     let params: AtomicUpdateParam = ctx.parameter_cursor().get()?;
 
+    upgrade_contract(
+        host,
+        logger,
+        params,
+        ctx.sender(),
+        ctx.metadata().slot_time(),
+    )
+}
+
+/// The parameter type for the contract function `atomicUpdateMany`.
+#[derive(Serialize, SchemaType, Clone)]
+#[concordium(transparent)]
+pub struct AtomicUpdateManyParams {
+    /// List of AtomicUpdateParam.
+    #[concordium(size_length = 2)]
+    pub entries: Vec<AtomicUpdateParam>,
+}
+
+/// An upgrader can natively upgrade several contracts in one transaction (e.g. the `UmbrellaFeeds` and the `StakingBank`
+/// contracts). The contracts are upgraded and registered in the provided order, if any upgrade fails, all upgrades and
+/// registrations of this transaction are reverted.
+/// If a minimum delay is set, the operation has to be queued instead.
+#[receive(
+    contract = "registry",
+    name = "atomicUpdateMany",
+    parameter = "AtomicUpdateManyParams",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn atomic_update_many<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure!(
+        host.state().has_role(&ctx.sender(), Role::Upgrader),
+        CustomContractError::UnauthorizedAccount
+    );
+
+    ensure!(
+        host.state().min_delay.millis() == 0,
+        CustomContractError::TimelockActive
+    );
+
+    let params: AtomicUpdateManyParams = ctx.parameter_cursor().get()?;
+
+    upgrade_contracts(
+        host,
+        logger,
+        params,
+        ctx.sender(),
+        ctx.metadata().slot_time(),
+    )
+}
+
+/// Internal function that natively upgrades and registers several contracts. Returning an error rejects the
+/// transaction, which reverts the upgrades and registrations done before the failing one.
+fn upgrade_contracts<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    params: AtomicUpdateManyParams,
+    sender: Address,
+    now: Timestamp,
+) -> Result<(), CustomContractError> {
+    for entry in params.entries {
+        upgrade_contract(host, logger, entry, sender, now)?;
+    }
+
+    Ok(())
+}
+
+/// Internal function that natively upgrades a contract, registers it and records its previous module reference for
+/// `rollback`.
+fn upgrade_contract<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    params: AtomicUpdateParam,
+    sender: Address,
+    now: Timestamp,
+) -> Result<(), CustomContractError> {
+    let contract_address = params.contract_address;
+
+    let (old_module, new_module) = native_upgrade(host, logger, params, sender, now)?;
+
+    // Upgrades to the same module are not recorded, so a rollback always restores other code.
+    if old_module != new_module {
+        host.state_mut()
+            .previous_modules
+            .entry(contract_address)
+            .or_default()
+            .modify(|modules| modules.push(old_module));
+    }
+
+    Ok(())
+}
+
+/// Internal function that natively upgrades a contract and registers it (executing the `upgradeNatively` and the
+/// `unregister` hooks). It returns the module references before and after the upgrade.
+fn native_upgrade<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    params: AtomicUpdateParam,
+    sender: Address,
+    now: Timestamp,
+) -> Result<(ModuleReference, ModuleReference), CustomContractError> {
+    let old_module = host
+        .contract_module_reference(params.contract_address)
+        .map_err(|_| CustomContractError::InvokeContractError)?;
+
+    let migrate = params
+        .migrate
+        .as_ref()
+        .map(|(entrypoint, _)| entrypoint.clone());
+
     let upgrade_params = UpgradeParams {
         module: params.module,
         migrate: params.migrate,
@@ -291,10 +903,16 @@ fn atomic_update<S: HasStateApi>(
         .ok_or(CustomContractError::InvokeContractError)?
         .get()?;
 
-    let old_contract = host
-        .state_mut()
-        .registry
-        .insert(name.clone(), params.contract_address);
+    // The module reference is queried after the upgrade.
+    ensure_approved(host, &name, params.contract_address)?;
+
+    let new_module = host
+        .contract_module_reference(params.contract_address)
+        .map_err(|_| CustomContractError::InvokeContractError)?;
+
+    let old_contract =
+        host.state_mut()
+            .register(name.clone(), params.contract_address, sender, now);
 
     // Only if another `old_contract` was already registered, execute the `unregister` hook
     if let Some(old_contract) = old_contract {
@@ -307,89 +925,615 @@ fn atomic_update<S: HasStateApi>(
         )?;
     }
 
-    // Log LogRegistered event
-    logger.log(&Event::LogRegistered(LogRegisteredEvent {
-        name,
-        destination: params.contract_address,
+    logger.log(&Event::ContractUpgraded(ContractUpgradedEvent {
+        contract_address: params.contract_address,
+        name: name.clone(),
+        old_module,
+        new_module,
+        migrate,
     }))?;
 
-    Ok(())
+    log_registered(logger, name, params.contract_address, old_contract)?;
+
+    Ok((old_module, new_module))
 }
 
-/// View function that returns the contract_address from a key name.
+/// The parameter type for the contract function `rollback`.
+#[derive(Debug, Serialize, SchemaType, Clone)]
+pub struct RollbackParams {
+    /// The contract address to roll back.
+    pub contract_address: ContractAddress,
+    /// Optional (reverse) migration entry point to call in the previous module after the rollback.
+    pub migrate: Option<(OwnedEntrypointName, OwnedParameter)>,
+}
+
+/// An upgrader can roll back the last native upgrade of a contract via this registry. The contract is natively upgraded
+/// to the module reference it ran before its last upgrade (via the `upgradeNatively` hook, optionally invoking a reverse
+/// migration) and registered again. Several upgrades can be rolled back one after the other.
+/// If a minimum delay is set, the operation has to be queued instead.
+///
+/// It rejects if:
+/// - Sender is not an upgrader.
+/// - The timelock is active.
+/// - It fails to parse the parameter.
+/// - The contract was not natively upgraded via this registry.
+/// - The upgrade, the migration or the registration fails.
 #[receive(
     contract = "registry",
-    name = "getAddress",
-    parameter = "String",
-    return_value = "ContractAddress"
+    name = "rollback",
+    parameter = "RollbackParams",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
 )]
-fn require_and_get_address<S: HasStateApi>(
+fn rollback<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
-    host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ReceiveResult<ContractAddress> {
-    let key_name: String = ctx.parameter_cursor().get()?;
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure!(
+        host.state().has_role(&ctx.sender(), Role::Upgrader),
+        CustomContractError::UnauthorizedAccount
+    );
 
-    host.state()
-        .registry
-        .get(&key_name)
-        .map(|s| *s)
-        .ok_or_else(|| CustomContractError::NameNotRegistered.into())
+    ensure!(
+        host.state().min_delay.millis() == 0,
+        CustomContractError::TimelockActive
+    );
+
+    let params: RollbackParams = ctx.parameter_cursor().get()?;
+
+    rollback_contract(
+        host,
+        logger,
+        params,
+        ctx.sender(),
+        ctx.metadata().slot_time(),
+    )
 }
 
-/// View function that returns the owner address.
+/// Internal function that natively upgrades a contract to the module reference it ran before its last upgrade and
+/// removes that module reference from the recorded modules.
+fn rollback_contract<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    params: RollbackParams,
+    sender: Address,
+    now: Timestamp,
+) -> Result<(), CustomContractError> {
+    let module = host
+        .state()
+        .previous_modules
+        .get(&params.contract_address)
+        .and_then(|modules| modules.last().copied())
+        .ok_or(CustomContractError::NoPreviousModule)?;
+
+    native_upgrade(
+        host,
+        logger,
+        AtomicUpdateParam {
+            module,
+            migrate: params.migrate,
+            contract_address: params.contract_address,
+        },
+        sender,
+        now,
+    )?;
+
+    host.state_mut()
+        .previous_modules
+        .entry(params.contract_address)
+        .and_modify(|modules| {
+            modules.pop();
+        });
+
+    Ok(())
+}
+
+/// View function that returns the module references a contract ran before its native upgrades via this registry
+/// (oldest first). The last module reference is restored by `rollback`.
 #[receive(
     contract = "registry",
-    name = "owner",
-    return_value = "Option<Address>"
+    name = "getPreviousModules",
+    parameter = "ContractAddress",
+    return_value = "Vec<ModuleReference>"
 )]
-fn owner<S: HasStateApi>(
-    _ctx: &impl HasReceiveContext,
+fn get_previous_modules<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
     host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ReceiveResult<Option<Address>> {
-    Ok(host.state().owner)
+) -> ReceiveResult<Vec<ModuleReference>> {
+    let contract_address: ContractAddress = ctx.parameter_cursor().get()?;
+
+    Ok(host
+        .state()
+        .previous_modules
+        .get(&contract_address)
+        .map(|modules| modules.clone())
+        .unwrap_or_default())
 }
 
-/// The owner can transfer the ownership to None. This means, the owner renounces the ownerhip of this contract.
+/// The parameter type for the contract function `removeAddresses`.
+#[derive(Debug, Serialize, SchemaType, Clone)]
+pub struct RemoveAddressesParams {
+    /// The keys/names to remove.
+    #[concordium(size_length = 2)]
+    pub names: Vec<String>,
+    /// Whether to execute the `unregister` hook of the removed contracts.
+    pub unregister: bool,
+}
+
+/// An importer can remove contract addresses from this registry by providing their keys/names. The `unregister` hook of
+/// the removed contracts is executed if requested. The function throws an error if a name is not registered.
+/// While a minimum delay is set, the `RemoveAddresses` operation has to be queued.
 #[receive(
     contract = "registry",
-    name = "renounceOwnership",
+    name = "removeAddresses",
+    parameter = "RemoveAddressesParams",
     error = "CustomContractError",
     enable_logger,
     mutable
 )]
-fn renounce_ownership<S: HasStateApi>(
+fn remove_addresses<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
     logger: &mut impl HasLogger,
 ) -> Result<(), CustomContractError> {
-    ensure_eq!(
-        ctx.sender(),
-        host.state().owner.ok_or(CustomContractError::NoOwner)?,
+    let sender = ctx.sender();
+
+    ensure!(
+        host.state().has_role(&sender, Role::Importer),
         CustomContractError::UnauthorizedAccount
     );
 
-    let previous_owner = host.state().owner;
-    host.state_mut().owner = None;
+    ensure!(
+        host.state().min_delay.millis() == 0,
+        CustomContractError::TimelockActive
+    );
 
-    // Log OwnershipTransferred event
-    logger.log(&Event::OwnershipTransferred(OwnershipTransferredEvent {
-        new_owner: None,
-        previous_owner,
-    }))?;
+    let params: RemoveAddressesParams = ctx.parameter_cursor().get()?;
+
+    unregister_addresses(host, logger, params, sender, ctx.metadata().slot_time())
+}
+
+/// Internal function that removes contract addresses from this registry and executes their `unregister` hooks if
+/// requested.
+fn unregister_addresses<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    params: RemoveAddressesParams,
+    sender: Address,
+    now: Timestamp,
+) -> Result<(), CustomContractError> {
+    for name in params.names {
+        let removed_contract = host
+            .state_mut()
+            .unregister(name.clone(), sender, now)
+            .ok_or(CustomContractError::NameNotRegistered)?;
+
+        if params.unregister {
+            // Execute the `unRegister()` hook
+            host.invoke_contract(
+                &removed_contract,
+                &Parameter::empty(),
+                EntrypointName::new_unchecked("unregister"),
+                Amount::zero(),
+            )?;
+        }
+
+        // Log LogUnregistered event
+        logger.log(&Event::LogUnregistered(LogUnregisteredEvent {
+            destination: removed_contract,
+            name,
+        }))?;
+    }
+
+    Ok(())
+}
+
+/// The code that is allowed to be registered under a key/name.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq, Clone)]
+pub struct Allowlist {
+    /// The approved module references.
+    pub modules: Vec<ModuleReference>,
+    /// The approved contract name (e.g. `init_umbrella_feeds`).
+    pub contract_name: OwnedContractName,
+}
+
+/// Internal function that ensures the module reference and the contract name of the contract match the allowlist of the
+/// key/name (if the key/name has an allowlist).
+fn ensure_approved<S: HasStateApi>(
+    host: &impl HasHost<State<S>, StateApiType = S>,
+    name: &String,
+    destination: ContractAddress,
+) -> Result<(), CustomContractError> {
+    let allowlist = match host.state().allowlist.get(name) {
+        Some(allowlist) => allowlist,
+        None => return Ok(()),
+    };
+
+    let module = host
+        .contract_module_reference(destination)
+        .map_err(|_| CustomContractError::UnapprovedModule)?;
+
+    ensure!(
+        allowlist.modules.contains(&module),
+        CustomContractError::UnapprovedModule
+    );
+
+    let contract_name = host
+        .contract_name(destination)
+        .map_err(|_| CustomContractError::UnapprovedContractName)?;
+
+    ensure_eq!(
+        contract_name,
+        allowlist.contract_name,
+        CustomContractError::UnapprovedContractName
+    );
 
     Ok(())
 }
 
-/// The owner can transfer the ownership to a new address.
+/// The parameter type for the contract function `setAllowlist`.
+#[derive(Debug, Serialize, SchemaType, Clone)]
+pub struct SetAllowlistParams {
+    /// The key/name of a contract.
+    pub name: String,
+    /// The code that is allowed to be registered under the key/name (`None` removes the allowlist).
+    pub allowlist: Option<Allowlist>,
+}
+
+/// An admin can pin a key/name to an allowlist of module references and a contract name. Once pinned, the
+/// `importAddresses`, `importContracts` and `atomicUpdate` entry points reject contracts of other code under this
+/// key/name. Already registered contract addresses are not checked. While a minimum delay is set, the `SetAllowlist`
+/// operation has to be queued.
 #[receive(
     contract = "registry",
-    name = "transferOwnership",
-    parameter = "Address",
+    name = "setAllowlist",
+    parameter = "SetAllowlistParams",
     error = "CustomContractError",
     enable_logger,
     mutable
 )]
-fn transfer_ownership<S: HasStateApi>(
+fn set_allowlist<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure!(
+        host.state().has_role(&ctx.sender(), Role::Admin),
+        CustomContractError::UnauthorizedAccount
+    );
+
+    ensure!(
+        host.state().min_delay.millis() == 0,
+        CustomContractError::TimelockActive
+    );
+
+    let params: SetAllowlistParams = ctx.parameter_cursor().get()?;
+
+    update_allowlist(host, logger, params)
+}
+
+/// Internal function that sets or removes the allowlist of a key/name.
+fn update_allowlist<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    params: SetAllowlistParams,
+) -> Result<(), CustomContractError> {
+    match &params.allowlist {
+        Some(allowlist) => {
+            let _ = host
+                .state_mut()
+                .allowlist
+                .insert(params.name.clone(), allowlist.clone());
+        }
+        None => host.state_mut().allowlist.remove(&params.name),
+    }
+
+    // Log AllowlistUpdated event
+    logger.log(&Event::AllowlistUpdated(AllowlistUpdatedEvent {
+        name: params.name,
+        allowlist: params.allowlist,
+    }))?;
+
+    Ok(())
+}
+
+/// View function that returns the allowlist of a key name (`None` if any contract can be registered under the name).
+#[receive(
+    contract = "registry",
+    name = "getAllowlist",
+    parameter = "String",
+    return_value = "Option<Allowlist>"
+)]
+fn get_allowlist<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Option<Allowlist>> {
+    let key_name: String = ctx.parameter_cursor().get()?;
+
+    Ok(host
+        .state()
+        .allowlist
+        .get(&key_name)
+        .map(|allowlist| allowlist.clone()))
+}
+
+/// View function that returns the contract_address from a key name.
+#[receive(
+    contract = "registry",
+    name = "getAddress",
+    parameter = "String",
+    return_value = "ContractAddress"
+)]
+fn require_and_get_address<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<ContractAddress> {
+    let key_name: String = ctx.parameter_cursor().get()?;
+
+    host.state()
+        .registry
+        .get(&key_name)
+        .map(|s| *s)
+        .ok_or_else(|| CustomContractError::NameNotRegistered.into())
+}
+
+/// View function that returns all contract addresses registered under (or removed from) a key name (oldest first). The
+/// history is empty if the name was never registered.
+#[receive(
+    contract = "registry",
+    name = "getHistory",
+    parameter = "String",
+    return_value = "Vec<HistoryEntry>"
+)]
+fn get_history<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<HistoryEntry>> {
+    let key_name: String = ctx.parameter_cursor().get()?;
+
+    Ok(host
+        .state()
+        .history
+        .get(&key_name)
+        .map(|history| history.clone())
+        .unwrap_or_default())
+}
+
+/// The parameter type for the contract function `getAddressAt`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct AddressAtParams {
+    /// The key/name of a contract.
+    pub name: String,
+    /// The point in time.
+    pub timestamp: Timestamp,
+}
+
+/// View function that returns the contract address that was registered under a key name at a point in time (`None` if
+/// the name was not registered yet or was removed).
+#[receive(
+    contract = "registry",
+    name = "getAddressAt",
+    parameter = "AddressAtParams",
+    return_value = "Option<ContractAddress>"
+)]
+fn get_address_at<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Option<ContractAddress>> {
+    let params: AddressAtParams = ctx.parameter_cursor().get()?;
+
+    let history = match host.state().history.get(&params.name) {
+        Some(history) => history,
+        None => return Ok(None),
+    };
+
+    Ok(history
+        .iter()
+        .rev()
+        .find(|entry| entry.timestamp <= params.timestamp)
+        .and_then(|entry| entry.destination))
+}
+
+/// View function that returns the contract addresses (`None` if the name is not registered) of the provided key names.
+#[receive(
+    contract = "registry",
+    name = "getManyAddresses",
+    parameter = "Vec<String>",
+    return_value = "Vec<Option<ContractAddress>>"
+)]
+fn get_many_addresses<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<Option<ContractAddress>>> {
+    let key_names: Vec<String> = ctx.parameter_cursor().get()?;
+
+    let registry = &host.state().registry;
+
+    Ok(key_names
+        .iter()
+        .map(|key_name| registry.get(key_name).map(|s| *s))
+        .collect())
+}
+
+/// The parameter type for the contract function `forward`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ForwardParams {
+    /// The key/name of the contract to invoke.
+    pub name: String,
+    /// The entry point to invoke.
+    pub entrypoint: OwnedEntrypointName,
+    /// The parameter of the invoke.
+    pub parameter: OwnedParameter,
+}
+
+/// The return_value type of the contract function `forward`. The bytes returned by the invoked entry point are written
+/// as they are (without a length prefix), so the return value can be parsed as the return value of the invoked entry
+/// point.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ForwardReturnValue(pub Vec<u8>);
+
+impl Serial for ForwardReturnValue {
+    fn serial<W: Write>(&self, out: &mut W) -> Result<(), W::Err> {
+        out.write_all(&self.0)
+    }
+}
+
+/// The entry points of the registered contracts that only accept invokes from this registry (`atomicUpdate` and
+/// `removeAddresses` hooks). Invokes of them are never forwarded.
+const REGISTRY_HOOKS: [&str; 2] = ["upgradeNatively", "unregister"];
+
+/// Function that resolves the contract address of a key name and forwards a read-only invoke of an entry point with the
+/// provided parameter to it (without CCD). The return value of the invoked entry point is returned, so integrators can
+/// e.g. query the `getPriceData` entry point of the `UmbrellaFeeds` contract with a single invoke.
+/// ATTENTION: The sender of the forwarded invoke is this registry contract, so the registry hooks (`upgradeNatively` and
+/// `unregister`) are never forwarded. Registered contracts must not authorize any other entry point by the registry as
+/// sender.
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+/// - The entry point is a registry hook.
+/// - The key name is not registered.
+/// - The invoke of the resolved contract fails.
+#[receive(
+    contract = "registry",
+    name = "forward",
+    parameter = "ForwardParams",
+    error = "CustomContractError"
+)]
+fn forward<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> Result<ForwardReturnValue, CustomContractError> {
+    let params: ForwardParams = ctx.parameter_cursor().get()?;
+
+    ensure!(
+        !REGISTRY_HOOKS
+            .iter()
+            .any(|hook| params.entrypoint.as_entrypoint_name()
+                == EntrypointName::new_unchecked(hook)),
+        CustomContractError::ForwardDenied
+    );
+
+    let destination = host
+        .state()
+        .registry
+        .get(&params.name)
+        .map(|s| *s)
+        .ok_or(CustomContractError::NameNotRegistered)?;
+
+    let return_value = host.invoke_contract_raw_read_only(
+        &destination,
+        params.parameter.as_parameter(),
+        params.entrypoint.as_entrypoint_name(),
+        Amount::zero(),
+    )?;
+
+    // V0 contracts do not return a value.
+    let mut bytes = Vec::new();
+
+    if let Some(mut return_value) = return_value {
+        bytes.resize(return_value.size() as usize, 0);
+        return_value.read_exact(&mut bytes)?;
+    }
+
+    Ok(ForwardReturnValue(bytes))
+}
+
+/// The parameter type for the contract functions `getAllEntries` and `getNames`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct PaginationParams {
+    /// The number of entries to skip.
+    pub offset: u32,
+    /// The maximum number of entries to return.
+    pub limit: u32,
+}
+
+/// Part of the return_value type for the contract function `getAllEntries`.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct RegistryEntry {
+    /// The key/name of a contract.
+    pub name: String,
+    /// The registered contract address.
+    pub destination: ContractAddress,
+}
+
+/// View function that returns up to `limit` registered entries (key name and contract address) after skipping `offset`
+/// entries. The entries are ordered by their serialized key names; an empty list is returned once `offset` is greater
+/// than the number of entries.
+#[receive(
+    contract = "registry",
+    name = "getAllEntries",
+    parameter = "PaginationParams",
+    return_value = "Vec<RegistryEntry>"
+)]
+fn get_all_entries<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<RegistryEntry>> {
+    let params: PaginationParams = ctx.parameter_cursor().get()?;
+
+    Ok(host
+        .state()
+        .registry
+        .iter()
+        .skip(params.offset as usize)
+        .take(params.limit as usize)
+        .map(|(name, destination)| RegistryEntry {
+            name: name.clone(),
+            destination: *destination,
+        })
+        .collect())
+}
+
+/// View function that returns up to `limit` registered key names after skipping `offset` names. The names are in the
+/// same order as in `getAllEntries`.
+#[receive(
+    contract = "registry",
+    name = "getNames",
+    parameter = "PaginationParams",
+    return_value = "Vec<String>"
+)]
+fn get_names<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<String>> {
+    let params: PaginationParams = ctx.parameter_cursor().get()?;
+
+    Ok(host
+        .state()
+        .registry
+        .iter()
+        .skip(params.offset as usize)
+        .take(params.limit as usize)
+        .map(|(name, _)| name.clone())
+        .collect())
+}
+
+/// View function that returns the owner address.
+#[receive(
+    contract = "registry",
+    name = "owner",
+    return_value = "Option<Address>"
+)]
+fn owner<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Option<Address>> {
+    Ok(host.state().owner)
+}
+
+/// The owner can transfer the ownership to None. This means, the owner renounces the ownerhip of this contract.
+/// Once a multisig is configured, the ownership can not be renounced anymore.
+#[receive(
+    contract = "registry",
+    name = "renounceOwnership",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn renounce_ownership<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
     logger: &mut impl HasLogger,
@@ -400,16 +1544,1099 @@ fn transfer_ownership<S: HasStateApi>(
         CustomContractError::UnauthorizedAccount
     );
 
-    let new_owner: Address = ctx.parameter_cursor().get()?;
+    ensure!(
+        host.state().signers.is_empty(),
+        CustomContractError::MultisigActive
+    );
 
     let previous_owner = host.state().owner;
-    host.state_mut().owner = Some(new_owner);
+    host.state_mut().owner = None;
+    // A pending ownership transfer can not be accepted anymore.
+    host.state_mut().pending_owner = None;
 
     // Log OwnershipTransferred event
     logger.log(&Event::OwnershipTransferred(OwnershipTransferredEvent {
-        new_owner: Some(new_owner),
+        new_owner: None,
         previous_owner,
     }))?;
 
     Ok(())
 }
+
+/// View function that returns the pending owner address.
+#[receive(
+    contract = "registry",
+    name = "pendingOwner",
+    return_value = "Option<Address>"
+)]
+fn pending_owner<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Option<Address>> {
+    Ok(host.state().pending_owner)
+}
+
+/// The owner can start the ownership transfer to a new address. The ownership is transferred once the new address invokes
+/// `acceptOwnership`. Starting a new transfer replaces the pending one. Once a multisig is configured, the transfer has
+/// to be proposed to the multisig (`TransferOwnership` operation).
+#[receive(
+    contract = "registry",
+    name = "transferOwnership",
+    parameter = "Address",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn transfer_ownership<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    let owner = host.state().owner.ok_or(CustomContractError::NoOwner)?;
+
+    ensure_eq!(
+        ctx.sender(),
+        owner,
+        CustomContractError::UnauthorizedAccount
+    );
+
+    ensure!(
+        host.state().signers.is_empty(),
+        CustomContractError::MultisigActive
+    );
+
+    let new_owner: Address = ctx.parameter_cursor().get()?;
+
+    start_ownership_transfer(host, logger, new_owner)
+}
+
+/// Internal function that records the pending owner of an ownership transfer. It rejects if the ownership was renounced.
+fn start_ownership_transfer<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    new_owner: Address,
+) -> Result<(), CustomContractError> {
+    ensure!(host.state().owner.is_some(), CustomContractError::NoOwner);
+
+    host.state_mut().pending_owner = Some(new_owner);
+
+    // Log OwnershipTransferPending event
+    logger.log(&Event::OwnershipTransferPending(
+        OwnershipTransferPendingEvent {
+            owner: host.state().owner,
+            pending_owner: Some(new_owner),
+        },
+    ))?;
+
+    Ok(())
+}
+
+/// The pending owner can accept the ownership transfer and becomes the new owner.
+#[receive(
+    contract = "registry",
+    name = "acceptOwnership",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn accept_ownership<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    let new_owner = host
+        .state()
+        .pending_owner
+        .ok_or(CustomContractError::NoPendingOwner)?;
+
+    ensure_eq!(
+        ctx.sender(),
+        new_owner,
+        CustomContractError::UnauthorizedAccount
+    );
+
+    let previous_owner = host.state().owner;
+    host.state_mut().owner = Some(new_owner);
+    host.state_mut().pending_owner = None;
+
+    // Log OwnershipTransferred event
+    logger.log(&Event::OwnershipTransferred(OwnershipTransferredEvent {
+        new_owner: Some(new_owner),
+        previous_owner,
+    }))?;
+
+    Ok(())
+}
+
+/// The owner can cancel the pending ownership transfer (as long as no multisig is configured).
+#[receive(
+    contract = "registry",
+    name = "cancelOwnershipTransfer",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn cancel_ownership_transfer<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    let owner = host.state().owner.ok_or(CustomContractError::NoOwner)?;
+
+    ensure_eq!(
+        ctx.sender(),
+        owner,
+        CustomContractError::UnauthorizedAccount
+    );
+
+    ensure!(
+        host.state().signers.is_empty(),
+        CustomContractError::MultisigActive
+    );
+
+    ensure!(
+        host.state().pending_owner.is_some(),
+        CustomContractError::NoPendingOwner
+    );
+
+    host.state_mut().pending_owner = None;
+
+    // Log OwnershipTransferPending event
+    logger.log(&Event::OwnershipTransferPending(
+        OwnershipTransferPendingEvent {
+            owner: Some(owner),
+            pending_owner: None,
+        },
+    ))?;
+
+    Ok(())
+}
+
+/// The parameter type for the contract functions `grantRole`, `revokeRole` and `hasRole`.
+#[derive(Debug, Serialize, SchemaType, Clone)]
+pub struct RoleParams {
+    /// The address.
+    pub address: Address,
+    /// The role.
+    pub role: Role,
+}
+
+/// An admin can grant a role to an address. While a minimum delay is set, the `GrantRole` operation has to be queued.
+/// The `Guardian` role can not be granted to the sender, so that an admin can not bypass the timelock on its own.
+#[receive(
+    contract = "registry",
+    name = "grantRole",
+    parameter = "RoleParams",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn grant_role<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    let sender = ctx.sender();
+
+    ensure!(
+        host.state().has_role(&sender, Role::Admin),
+        CustomContractError::UnauthorizedAccount
+    );
+
+    ensure!(
+        host.state().min_delay.millis() == 0,
+        CustomContractError::TimelockActive
+    );
+
+    let params: RoleParams = ctx.parameter_cursor().get()?;
+
+    add_role(host, logger, params, sender)
+}
+
+/// Internal function that grants a role to an address. It rejects if the `Guardian` role is granted to the sender.
+fn add_role<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    params: RoleParams,
+    sender: Address,
+) -> Result<(), CustomContractError> {
+    ensure!(
+        params.role != Role::Guardian || params.address != sender,
+        CustomContractError::UnauthorizedAccount
+    );
+
+    host.state_mut().roles.insert((params.address, params.role));
+
+    // Log RoleGranted event
+    logger.log(&Event::RoleGranted(RoleGrantedEvent {
+        address: params.address,
+        role: params.role,
+        sender,
+    }))?;
+
+    Ok(())
+}
+
+/// An admin can revoke a role from an address. A guardian can revoke the `Upgrader` and the `Importer` roles. While a
+/// minimum delay is set, admins have to queue the `RevokeRole` operation, guardians can still revoke these roles
+/// directly (e.g. if a key got compromised).
+/// The roles of the owner can not be revoked, the owner has all roles except `Guardian` until the ownership is transferred
+/// or renounced.
+#[receive(
+    contract = "registry",
+    name = "revokeRole",
+    parameter = "RoleParams",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn revoke_role<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    let sender = ctx.sender();
+
+    let params: RoleParams = ctx.parameter_cursor().get()?;
+
+    let is_guardian_revocable = matches!(params.role, Role::Upgrader | Role::Importer);
+
+    let is_guardian_revocation =
+        is_guardian_revocable && host.state().has_role(&sender, Role::Guardian);
+
+    ensure!(
+        host.state().has_role(&sender, Role::Admin) || is_guardian_revocation,
+        CustomContractError::UnauthorizedAccount
+    );
+
+    ensure!(
+        is_guardian_revocation || host.state().min_delay.millis() == 0,
+        CustomContractError::TimelockActive
+    );
+
+    remove_role(host, logger, params, sender)
+}
+
+/// Internal function that revokes a role from an address.
+fn remove_role<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    params: RoleParams,
+    sender: Address,
+) -> Result<(), CustomContractError> {
+    host.state_mut()
+        .roles
+        .remove(&(params.address, params.role));
+
+    // Log RoleRevoked event
+    logger.log(&Event::RoleRevoked(RoleRevokedEvent {
+        address: params.address,
+        role: params.role,
+        sender,
+    }))?;
+
+    Ok(())
+}
+
+/// View function that returns a boolean if the address has the role (the owner has all roles except `Guardian`).
+#[receive(
+    contract = "registry",
+    name = "hasRole",
+    parameter = "RoleParams",
+    return_value = "bool"
+)]
+fn has_role<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<bool> {
+    let params: RoleParams = ctx.parameter_cursor().get()?;
+
+    Ok(host.state().has_role(&params.address, params.role))
+}
+
+/// The parameter type for the contract function `setMultisig`.
+#[derive(Debug, Serialize, SchemaType, Clone)]
+pub struct MultisigParams {
+    /// The signers of the multisig.
+    pub signers: Vec<Address>,
+    /// The number of approvals needed to execute a proposal.
+    pub threshold: u8,
+    /// The duration a proposal can be approved before it expires.
+    pub proposal_duration: Duration,
+}
+
+/// An admin can set the signers, the threshold and the proposal duration of the multisig as long as no multisig is
+/// configured. Afterwards, the multisig is changed by proposing the `SetMultisig` operation. Approvals of addresses that
+/// are not signers anymore do not count toward the threshold.
+#[receive(
+    contract = "registry",
+    name = "setMultisig",
+    parameter = "MultisigParams",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn set_multisig<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure!(
+        host.state().has_role(&ctx.sender(), Role::Admin),
+        CustomContractError::UnauthorizedAccount
+    );
+
+    ensure!(
+        host.state().signers.is_empty(),
+        CustomContractError::MultisigActive
+    );
+
+    let params: MultisigParams = ctx.parameter_cursor().get()?;
+
+    update_multisig(host, logger, params)
+}
+
+/// Internal function that validates and sets the multisig configuration.
+fn update_multisig<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    params: MultisigParams,
+) -> Result<(), CustomContractError> {
+    // The approvals are counted as `u8`.
+    ensure!(
+        params.signers.len() <= usize::from(u8::MAX),
+        CustomContractError::TooManySigners
+    );
+
+    ensure!(
+        params.threshold > 0 && usize::from(params.threshold) <= params.signers.len(),
+        CustomContractError::InvalidThreshold
+    );
+
+    for (i, signer) in params.signers.iter().enumerate() {
+        ensure!(
+            !params.signers[..i].contains(signer),
+            CustomContractError::DuplicateSigner
+        );
+    }
+
+    let state = host.state_mut();
+    state.signers = params.signers.clone();
+    state.threshold = params.threshold;
+    state.proposal_duration = params.proposal_duration;
+
+    // Log MultisigUpdated event
+    logger.log(&Event::MultisigUpdated(MultisigUpdatedEvent {
+        signers: params.signers,
+        threshold: params.threshold,
+        proposal_duration: params.proposal_duration,
+    }))?;
+
+    Ok(())
+}
+
+/// View function that returns the multisig configuration.
+#[receive(
+    contract = "registry",
+    name = "multisig",
+    return_value = "MultisigParams"
+)]
+fn multisig<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<MultisigParams> {
+    let state = host.state();
+
+    Ok(MultisigParams {
+        signers: state.signers.clone(),
+        threshold: state.threshold,
+        proposal_duration: state.proposal_duration,
+    })
+}
+
+/// The operations that can be proposed to the multisig.
+#[derive(Serialize, SchemaType, Clone)]
+pub enum Operation {
+    /// Executes `importAddresses`.
+    ImportAddresses(ImportAddressesParams),
+    /// Executes `importContracts`.
+    ImportContracts(ImportContractsParam),
+    /// Executes `atomicUpdate`.
+    AtomicUpdate(AtomicUpdateParam),
+    /// Executes `transferOwnership`.
+    TransferOwnership(Address),
+    /// Executes `atomicUpdateMany`.
+    AtomicUpdateMany(AtomicUpdateManyParams),
+    /// Executes `rollback`.
+    Rollback(RollbackParams),
+    /// Approves the `upgrade` of this registry.
+    UpgradeRegistry(UpgradeParams),
+    /// Changes the multisig configuration (`setMultisig`).
+    SetMultisig(MultisigParams),
+    /// Executes `grantRole`.
+    GrantRole(RoleParams),
+    /// Executes `revokeRole`.
+    RevokeRole(RoleParams),
+    /// Executes `setAllowlist`.
+    SetAllowlist(SetAllowlistParams),
+    /// Executes `setMinDelay`.
+    SetMinDelay(Duration),
+    /// Executes `removeAddresses`.
+    RemoveAddresses(RemoveAddressesParams),
+}
+
+impl Operation {
+    /// Internal function that returns the role needed to execute the operation directly or to queue it (`None` if the
+    /// operation can not be timelocked).
+    fn timelock_role(&self) -> Option<Role> {
+        match self {
+            Operation::ImportAddresses(_)
+            | Operation::ImportContracts(_)
+            | Operation::RemoveAddresses(_) => Some(Role::Importer),
+            Operation::AtomicUpdate(_)
+            | Operation::AtomicUpdateMany(_)
+            | Operation::Rollback(_)
+            | Operation::UpgradeRegistry(_) => Some(Role::Upgrader),
+            Operation::GrantRole(_)
+            | Operation::RevokeRole(_)
+            | Operation::SetAllowlist(_)
+            | Operation::SetMinDelay(_) => Some(Role::Admin),
+            Operation::TransferOwnership(_) | Operation::SetMultisig(_) => None,
+        }
+    }
+}
+
+/// A proposed operation that has not been executed yet.
+#[derive(Serialize, SchemaType, Clone)]
+pub struct Proposal {
+    /// The operation to execute.
+    pub operation: Operation,
+    /// The signer that proposed the operation.
+    pub proposer: Address,
+    /// The addresses that approved the proposal.
+    pub approvals: Vec<Address>,
+    /// The time after which the proposal can not be approved anymore.
+    pub expiry: Timestamp,
+}
+
+/// A signer can propose an operation. The proposal counts as approved by the proposer and is executed immediately if
+/// the threshold is one. The function returns the id of the proposal.
+#[receive(
+    contract = "registry",
+    name = "propose",
+    parameter = "Operation",
+    return_value = "u64",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn propose<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<u64, CustomContractError> {
+    let proposer = ctx.sender();
+
+    ensure!(
+        host.state().signers.contains(&proposer),
+        CustomContractError::UnauthorizedAccount
+    );
+
+    let operation: Operation = ctx.parameter_cursor().get()?;
+
+    let now = ctx.metadata().slot_time();
+
+    let state = host.state_mut();
+
+    let proposal_id = state.next_proposal_id;
+    state.next_proposal_id += 1;
+
+    let expiry = now
+        .checked_add(state.proposal_duration)
+        .unwrap_or(Timestamp::from_timestamp_millis(u64::MAX));
+
+    let _ = state.proposals.insert(
+        proposal_id,
+        Proposal {
+            operation,
+            proposer,
+            approvals: Vec::new(),
+            expiry,
+        },
+    );
+
+    // Log ProposalCreated event
+    logger.log(&Event::ProposalCreated(ProposalCreatedEvent {
+        proposal_id,
+        proposer,
+        expiry,
+    }))?;
+
+    approve_proposal(host, logger, proposal_id, proposer, now)?;
+
+    Ok(proposal_id)
+}
+
+/// A signer can approve a proposal that has not expired. The proposal is executed once the threshold is reached.
+#[receive(
+    contract = "registry",
+    name = "approve",
+    parameter = "u64",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn approve<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    let signer = ctx.sender();
+
+    ensure!(
+        host.state().signers.contains(&signer),
+        CustomContractError::UnauthorizedAccount
+    );
+
+    let proposal_id: u64 = ctx.parameter_cursor().get()?;
+
+    approve_proposal(
+        host,
+        logger,
+        proposal_id,
+        signer,
+        ctx.metadata().slot_time(),
+    )
+}
+
+/// Internal function that records the approval of a signer and executes the proposal once the threshold is reached.
+fn approve_proposal<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    proposal_id: u64,
+    signer: Address,
+    now: Timestamp,
+) -> Result<(), CustomContractError> {
+    let approvals = {
+        let mut proposal = host
+            .state_mut()
+            .proposals
+            .get_mut(&proposal_id)
+            .ok_or(CustomContractError::ProposalNotFound)?;
+
+        ensure!(now <= proposal.expiry, CustomContractError::ProposalExpired);
+
+        ensure!(
+            !proposal.approvals.contains(&signer),
+            CustomContractError::AlreadyApproved
+        );
+
+        proposal.approvals.push(signer);
+        proposal.approvals.clone()
+    };
+
+    let state = host.state();
+
+    // Only approvals of current signers count toward the threshold.
+    let approvals = u8::try_from(
+        approvals
+            .iter()
+            .filter(|approval| state.signers.contains(approval))
+            .count(),
+    )
+    .map_err(|_| CustomContractError::TooManySigners)?;
+
+    let threshold = state.threshold;
+
+    // Log ProposalApproved event
+    logger.log(&Event::ProposalApproved(ProposalApprovedEvent {
+        proposal_id,
+        signer,
+        approvals,
+    }))?;
+
+    if approvals < threshold {
+        return Ok(());
+    }
+
+    let proposal = host
+        .state_mut()
+        .proposals
+        .remove_and_get(&proposal_id)
+        .ok_or(CustomContractError::ProposalNotFound)?;
+
+    // Timelocked operations are queued once a minimum delay is set.
+    if proposal.operation.timelock_role().is_some() && host.state().min_delay.millis() > 0 {
+        queue_operation(host, logger, proposal.operation, proposal.proposer, now)?;
+    } else {
+        execute_operation(host, logger, proposal.operation, proposal.proposer, now)?;
+    }
+
+    // Log ProposalExecuted event
+    logger.log(&Event::ProposalExecuted(ProposalExecutedEvent {
+        proposal_id,
+    }))?;
+
+    Ok(())
+}
+
+/// Anyone can remove expired proposals, since they can not be approved anymore.
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+/// - A proposal does not exist.
+/// - A proposal has not expired yet.
+#[receive(
+    contract = "registry",
+    name = "removeExpiredProposals",
+    parameter = "Vec<u64>",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn remove_expired_proposals<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    let proposal_ids: Vec<u64> = ctx.parameter_cursor().get()?;
+
+    let now = ctx.metadata().slot_time();
+
+    for proposal_id in proposal_ids {
+        let proposal = host
+            .state_mut()
+            .proposals
+            .remove_and_get(&proposal_id)
+            .ok_or(CustomContractError::ProposalNotFound)?;
+
+        ensure!(
+            now > proposal.expiry,
+            CustomContractError::ProposalNotExpired
+        );
+
+        // Log ProposalRemoved event
+        logger.log(&Event::ProposalRemoved(ProposalRemovedEvent {
+            proposal_id,
+        }))?;
+    }
+
+    Ok(())
+}
+
+/// View function that returns a proposal that has not been executed yet.
+#[receive(
+    contract = "registry",
+    name = "getProposal",
+    parameter = "u64",
+    return_value = "Option<Proposal>"
+)]
+fn get_proposal<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Option<Proposal>> {
+    let proposal_id: u64 = ctx.parameter_cursor().get()?;
+
+    Ok(host
+        .state()
+        .proposals
+        .get(&proposal_id)
+        .map(|proposal| proposal.clone()))
+}
+
+/// Internal function that executes an operation.
+fn execute_operation<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    operation: Operation,
+    sender: Address,
+    now: Timestamp,
+) -> Result<(), CustomContractError> {
+    match operation {
+        Operation::ImportAddresses(params) => register_addresses(host, logger, params, sender, now),
+        Operation::ImportContracts(params) => register_contracts(host, logger, params, sender, now),
+        Operation::AtomicUpdate(params) => upgrade_contract(host, logger, params, sender, now),
+        Operation::TransferOwnership(new_owner) => {
+            start_ownership_transfer(host, logger, new_owner)
+        }
+        Operation::AtomicUpdateMany(params) => upgrade_contracts(host, logger, params, sender, now),
+        Operation::Rollback(params) => rollback_contract(host, logger, params, sender, now),
+        Operation::UpgradeRegistry(params) => {
+            // The upgrade itself is executed by the `low_level` `upgrade` entry point, since a migration might change
+            // the shape of the state that this function writes back.
+            host.state_mut().approved_upgrade = Some(params);
+            Ok(())
+        }
+        Operation::SetMultisig(params) => update_multisig(host, logger, params),
+        Operation::GrantRole(params) => add_role(host, logger, params, sender),
+        Operation::RevokeRole(params) => remove_role(host, logger, params, sender),
+        Operation::SetAllowlist(params) => update_allowlist(host, logger, params),
+        Operation::SetMinDelay(min_delay) => update_min_delay(host, logger, min_delay),
+        Operation::RemoveAddresses(params) => {
+            unregister_addresses(host, logger, params, sender, now)
+        }
+    }
+}
+
+/// An admin can set the minimum delay between queueing and executing the `importAddresses`, `importContracts`,
+/// `removeAddresses`, `atomicUpdate`, `atomicUpdateMany`, `rollback`, `upgrade` (`UpgradeRegistry`), `grantRole`, `revokeRole`,
+/// `setAllowlist` and `setMinDelay` operations. A delay of zero disables the timelock. The delay does not change the eta
+/// of operations that are already queued. While a minimum delay is set, it can only be raised directly, lowering it
+/// requires queueing the `SetMinDelay` operation.
+#[receive(
+    contract = "registry",
+    name = "setMinDelay",
+    parameter = "Duration",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn set_min_delay<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure!(
+        host.state().has_role(&ctx.sender(), Role::Admin),
+        CustomContractError::UnauthorizedAccount
+    );
+
+    let min_delay: Duration = ctx.parameter_cursor().get()?;
+
+    ensure!(
+        min_delay >= host.state().min_delay,
+        CustomContractError::TimelockActive
+    );
+
+    update_min_delay(host, logger, min_delay)
+}
+
+/// Internal function that sets the minimum delay of the timelock.
+fn update_min_delay<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    min_delay: Duration,
+) -> Result<(), CustomContractError> {
+    host.state_mut().min_delay = min_delay;
+
+    // Log MinDelayUpdated event
+    logger.log(&Event::MinDelayUpdated(MinDelayUpdatedEvent { min_delay }))?;
+
+    Ok(())
+}
+
+/// View function that returns the minimum delay of the timelock.
+#[receive(contract = "registry", name = "minDelay", return_value = "Duration")]
+fn min_delay<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Duration> {
+    Ok(host.state().min_delay)
+}
+
+/// An operation that is queued in the timelock.
+#[derive(Serialize, SchemaType, Clone)]
+pub struct QueuedOperation {
+    /// The operation to execute.
+    pub operation: Operation,
+    /// The address that queued the operation (the proposer for multisig proposals).
+    pub sender: Address,
+    /// The time after which the operation can be executed.
+    pub eta: Timestamp,
+}
+
+/// An importer can queue the `ImportAddresses`, `ImportContracts` and `RemoveAddresses` operations, an upgrader can queue the
+/// `AtomicUpdate`, `AtomicUpdateMany`, `Rollback` and `UpgradeRegistry` operations and an admin can queue the
+/// `GrantRole`, `RevokeRole`, `SetAllowlist` and `SetMinDelay` operations. The operation can be executed once the minimum
+/// delay has passed. The function returns the id of the queued operation.
+#[receive(
+    contract = "registry",
+    name = "queue",
+    parameter = "Operation",
+    return_value = "u64",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn queue<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<u64, CustomContractError> {
+    let sender = ctx.sender();
+
+    let operation: Operation = ctx.parameter_cursor().get()?;
+
+    let role = operation
+        .timelock_role()
+        .ok_or(CustomContractError::InvalidOperation)?;
+
+    ensure!(
+        host.state().has_role(&sender, role),
+        CustomContractError::UnauthorizedAccount
+    );
+
+    queue_operation(host, logger, operation, sender, ctx.metadata().slot_time())
+}
+
+/// Internal function that queues an operation with an eta of now plus the minimum delay.
+fn queue_operation<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    operation: Operation,
+    sender: Address,
+    now: Timestamp,
+) -> Result<u64, CustomContractError> {
+    let state = host.state_mut();
+
+    let operation_id = state.next_operation_id;
+    state.next_operation_id += 1;
+
+    let eta = now
+        .checked_add(state.min_delay)
+        .unwrap_or(Timestamp::from_timestamp_millis(u64::MAX));
+
+    let _ = state.queued_operations.insert(
+        operation_id,
+        QueuedOperation {
+            operation,
+            sender,
+            eta,
+        },
+    );
+
+    // Log OperationQueued event
+    logger.log(&Event::OperationQueued(OperationQueuedEvent {
+        operation_id,
+        sender,
+        eta,
+    }))?;
+
+    Ok(operation_id)
+}
+
+/// Anyone can execute a queued operation once its eta has passed.
+#[receive(
+    contract = "registry",
+    name = "execute",
+    parameter = "u64",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn execute<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    let operation_id: u64 = ctx.parameter_cursor().get()?;
+
+    let queued_operation = host
+        .state_mut()
+        .queued_operations
+        .remove_and_get(&operation_id)
+        .ok_or(CustomContractError::OperationNotFound)?;
+
+    ensure!(
+        ctx.metadata().slot_time() >= queued_operation.eta,
+        CustomContractError::TimelockNotReady
+    );
+
+    execute_operation(
+        host,
+        logger,
+        queued_operation.operation,
+        queued_operation.sender,
+        ctx.metadata().slot_time(),
+    )?;
+
+    // Log OperationExecuted event
+    logger.log(&Event::OperationExecuted(OperationExecutedEvent {
+        operation_id,
+    }))?;
+
+    Ok(())
+}
+
+/// The address that queued an operation or a guardian can cancel a queued operation.
+#[receive(
+    contract = "registry",
+    name = "cancel",
+    parameter = "u64",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn cancel<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    let sender = ctx.sender();
+
+    let operation_id: u64 = ctx.parameter_cursor().get()?;
+
+    let queued_operation = host
+        .state_mut()
+        .queued_operations
+        .remove_and_get(&operation_id)
+        .ok_or(CustomContractError::OperationNotFound)?;
+
+    ensure!(
+        queued_operation.sender == sender || host.state().has_role(&sender, Role::Guardian),
+        CustomContractError::UnauthorizedAccount
+    );
+
+    // Log OperationCancelled event
+    logger.log(&Event::OperationCancelled(OperationCancelledEvent {
+        operation_id,
+        sender,
+    }))?;
+
+    Ok(())
+}
+
+/// A guardian can execute the `ImportAddresses`, `ImportContracts`, `RemoveAddresses`, `AtomicUpdate`,
+/// `AtomicUpdateMany`, `Rollback` and `UpgradeRegistry` operations in an emergency without queueing them. The operations of admins (e.g. `GrantRole` or
+/// `SetMinDelay`) can not bypass the timelock.
+#[receive(
+    contract = "registry",
+    name = "emergencyExecute",
+    parameter = "Operation",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn emergency_execute<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    let sender = ctx.sender();
+
+    ensure!(
+        host.state().has_role(&sender, Role::Guardian),
+        CustomContractError::UnauthorizedAccount
+    );
+
+    let operation: Operation = ctx.parameter_cursor().get()?;
+
+    ensure!(
+        matches!(
+            operation.timelock_role(),
+            Some(Role::Importer | Role::Upgrader)
+        ),
+        CustomContractError::InvalidOperation
+    );
+
+    execute_operation(host, logger, operation, sender, ctx.metadata().slot_time())?;
+
+    // Log EmergencyExecuted event
+    logger.log(&Event::EmergencyExecuted(EmergencyExecutedEvent { sender }))?;
+
+    Ok(())
+}
+
+/// View function that returns a queued operation that has not been executed or cancelled yet.
+#[receive(
+    contract = "registry",
+    name = "getQueuedOperation",
+    parameter = "u64",
+    return_value = "Option<QueuedOperation>"
+)]
+fn get_queued_operation<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Option<QueuedOperation>> {
+    let operation_id: u64 = ctx.parameter_cursor().get()?;
+
+    Ok(host
+        .state()
+        .queued_operations
+        .get(&operation_id)
+        .map(|queued_operation| queued_operation.clone()))
+}
+
+/// An upgrader can upgrade this registry contract to a new module and call optionally a migration function after the
+/// upgrade. The contract address of the registry stays the same for all consumers.
+/// If a minimum delay is set, the `UpgradeRegistry` operation has to be queued (or proposed to the multisig) and executed
+/// first. The upgrade then has to match the approved upgrade, which is consumed.
+///
+/// It rejects if:
+/// - Sender is not an upgrader.
+/// - It fails to parse the parameter.
+/// - The timelock is active and the upgrade was not approved.
+/// - If the upgrade fails.
+/// - If the migration invoke fails.
+///
+/// This function is marked as `low_level`. This is **necessary** since the
+/// high-level mutable functions store the state of the contract at the end of
+/// execution. This conflicts with migration since the shape of the state
+/// **might** be changed by the migration function. If the state is then written
+/// by this function it would overwrite the state stored by the migration
+/// function.
+#[receive(
+    contract = "registry",
+    name = "upgrade",
+    parameter = "UpgradeParams",
+    error = "CustomContractError",
+    low_level
+)]
+fn upgrade<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<S>,
+) -> Result<(), CustomContractError> {
+    // Read the top-level contract state.
+    let mut state: State<S> = host.state().read_root()?;
+
+    // Only upgraders can upgrade this contract.
+    ensure!(
+        state.has_role(&ctx.sender(), Role::Upgrader),
+        CustomContractError::UnauthorizedAccount
+    );
+
+    // Parse the parameter.
+    let param: UpgradeParams = ctx.parameter_cursor().get()?;
+
+    let approved = state.approved_upgrade.as_ref() == Some(&param);
+
+    ensure!(
+        approved || state.min_delay.millis() == 0,
+        CustomContractError::TimelockActive
+    );
+
+    // Consume the approval before the migration reads the state.
+    if approved {
+        state.approved_upgrade = None;
+        host.state_mut().write_root(&state);
+    }
+
+    // Trigger the upgrade.
+    host.upgrade(param.module)?;
+
+    // Call the migration function if provided.
+    if let Some((func, parameters)) = param.migrate {
+        host.invoke_contract_raw(
+            &ctx.self_address(),
+            parameters.as_parameter(),
+            func.as_entrypoint_name(),
+            Amount::zero(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// View function that returns the upgrade of this registry approved by an executed `UpgradeRegistry` operation (`None` if
+/// there is none).
+#[receive(
+    contract = "registry",
+    name = "approvedUpgrade",
+    return_value = "Option<UpgradeParams>"
+)]
+fn approved_upgrade<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Option<UpgradeParams>> {
+    Ok(host.state().approved_upgrade.clone())
+}
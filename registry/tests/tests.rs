@@ -1,18 +1,191 @@
 use concordium_smart_contract_testing::*;
-use registry::{ImportAddressesParam, ImportAddressesParams, OwnershipTransferredEvent};
-use registry::{ImportContractsParam, LogRegisteredEvent};
+use registry::{
+    AddressAtParams, HistoryEntry, ImportAddressesParam, ImportAddressesParams,
+    LogUnregisteredEvent, MinDelayUpdatedEvent, OperationCancelledEvent, OperationExecutedEvent,
+    OperationQueuedEvent, OwnershipTransferPendingEvent, OwnershipTransferredEvent,
+    PaginationParams, QueuedOperation, RegistryEntry, RemoveAddressesParams, RollbackParams,
+};
+use registry::{
+    Allowlist, AllowlistUpdatedEvent, AtomicUpdateManyParams, AtomicUpdateParam,
+    ContractReplacedEvent, ForwardParams, ImportContractsParam, LogRegisteredEvent, MultisigParams,
+    MultisigUpdatedEvent, Operation, Proposal, ProposalApprovedEvent, ProposalCreatedEvent,
+    ProposalExecutedEvent, ProposalRemovedEvent, Role, RoleGrantedEvent, RoleParams,
+    RoleRevokedEvent, SetAllowlistParams, UpgradeParams,
+};
 
 const ACC_ADDR_OWNER: AccountAddress = AccountAddress([77u8; 32]);
 const OTHER_ACCOUNT: AccountAddress = AccountAddress([1u8; 32]);
+const GUARDIAN_ACCOUNT: AccountAddress = AccountAddress([2u8; 32]);
 
 const ACC_INITIAL_BALANCE: Amount = Amount::from_ccd(1000);
 
+/// Returns the reject reason of a failed invoke if the contract rejected on its own.
+fn reject_reason(error: &ContractInvokeError) -> Option<i32> {
+    match &error.kind {
+        ContractInvokeErrorKind::ExecutionError {
+            failure_kind: InvokeFailure::ContractReject { code, .. },
+        } => Some(*code),
+        _ => None,
+    }
+}
+
+/// Invokes `owner`.
+fn get_owner(chain: &Chain, registry: ContractAddress) -> Option<Address> {
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.owner".to_string()),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query owner address");
+
+    from_bytes(&invoke.return_value).expect("Should return a valid result")
+}
+
+/// Invokes `pendingOwner`.
+fn get_pending_owner(chain: &Chain, registry: ContractAddress) -> Option<Address> {
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.pendingOwner".to_string()),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query pending owner address");
+
+    from_bytes(&invoke.return_value).expect("Should return a valid result")
+}
+
+/// Invokes `hasRole`.
+fn has_role(chain: &Chain, registry: ContractAddress, account: AccountAddress, role: Role) -> bool {
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.hasRole".to_string()),
+                message: OwnedParameter::from_serial(&RoleParams {
+                    address: Address::Account(account),
+                    role,
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query hasRole");
+
+    from_bytes(&invoke.return_value).expect("Should return a valid result")
+}
+
+/// Invokes an entry point of the registry as `sender`.
+fn update_registry<P: concordium_std::Serial>(
+    chain: &mut Chain,
+    sender: AccountAddress,
+    registry: ContractAddress,
+    entrypoint: &str,
+    parameter: &P,
+) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+    chain.contract_update(
+        Signer::with_one_key(),
+        sender,
+        Address::Account(sender),
+        Energy::from(10000),
+        UpdateContractPayload {
+            address: registry,
+            receive_name: OwnedReceiveName::new_unchecked(format!("registry.{entrypoint}")),
+            message: OwnedParameter::from_serial(parameter)
+                .expect("`input_parameter` should be a valid inut parameter"),
+            amount: Amount::from_ccd(0),
+        },
+    )
+}
+
+/// Invokes a view function of the registry.
+fn view_registry<P: concordium_std::Serial, R: concordium_std::Deserial>(
+    chain: &Chain,
+    registry: ContractAddress,
+    entrypoint: &str,
+    parameter: &P,
+) -> R {
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked(format!("registry.{entrypoint}")),
+                message: OwnedParameter::from_serial(parameter)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query the view function");
+
+    from_bytes(&invoke.return_value).expect("Should return a valid result")
+}
+
+/// Deploys and initializes the `dummy_contract` contract and imports it into the registry under the name `MyName`.
+fn register_dummy_contract(chain: &mut Chain, registry: ContractAddress) -> ContractAddress {
+    // Deploying 'dummy_contract' contract.
+
+    let deployment = chain
+        .module_deploy_v1(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            module_load_v1("../dummy-contract/dummy_contract.wasm.v1")
+                .expect("`dummy_contract.wasm.v1` module should be loaded"),
+        )
+        .expect("`dummy_contract.wasm.v1` deployment should always succeed");
+
+    let initialization_dummy_contract = chain
+        .contract_init(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Energy::from(10000),
+            InitContractPayload {
+                amount: Amount::zero(),
+                mod_ref: deployment.module_reference,
+                init_name: OwnedContractName::new_unchecked("init_dummy_contract".to_string()),
+                param: OwnedParameter::empty(),
+            },
+        )
+        .expect("Initialization of `dummy_contract` should always succeed");
+
+    update_registry(
+        chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "importContracts",
+        &ImportContractsParam {
+            entries: vec![initialization_dummy_contract.contract_address],
+        },
+    )
+    .expect("Should be able to importContracts");
+
+    initialization_dummy_contract.contract_address
+}
+
 fn setup_chain_and_contract() -> (Chain, ContractInitSuccess) {
     let mut chain = Chain::new();
 
     // Creating accounts.
     chain.create_account(Account::new(ACC_ADDR_OWNER, ACC_INITIAL_BALANCE));
     chain.create_account(Account::new(OTHER_ACCOUNT, ACC_INITIAL_BALANCE));
+    chain.create_account(Account::new(GUARDIAN_ACCOUNT, ACC_INITIAL_BALANCE));
 
     // Deploying 'registry' contract.
 
@@ -129,6 +302,7 @@ fn test_import_addresses() {
         LogRegisteredEvent {
             name: String::from("Contract1"),
             destination: umbrella_feeds_contract,
+            previous: None,
         },
         "LogRegistered event is wrong"
     );
@@ -147,6 +321,7 @@ fn test_import_addresses() {
         LogRegisteredEvent {
             name: String::from("Contract2"),
             destination: staking_bank_contract,
+            previous: None,
         },
         "LogRegistered event is wrong"
     );
@@ -228,7 +403,7 @@ fn test_import_contracts() {
 
     // Checking logged event.
     let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
-    let event = &events[1].1[0];
+    let event = &events[2].1[0];
 
     // checking event tag.
     assert_eq!(event.as_ref()[0], 0, "Event tag is wrong");
@@ -242,6 +417,7 @@ fn test_import_contracts() {
         LogRegisteredEvent {
             name: String::from("MyName"),
             destination: initialization_dummy_contract.contract_address,
+            previous: None,
         },
         "LogRegistered event is wrong"
     );
@@ -321,41 +497,98 @@ fn test_owner_functionalities() {
     let event = &events[0].1[0];
 
     // Checking event tag.
-    assert_eq!(event.as_ref()[0], 1, "Event tag is wrong");
+    assert_eq!(event.as_ref()[0], 2, "Event tag is wrong");
 
     // Removing the tag byte at the beginning of the event.
-    let event_struct: OwnershipTransferredEvent =
+    let event_struct: OwnershipTransferPendingEvent =
         from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
 
     assert_eq!(
         event_struct,
-        OwnershipTransferredEvent {
-            new_owner: Some(Address::from(OTHER_ACCOUNT)),
-            previous_owner: Some(Address::from(ACC_ADDR_OWNER)),
+        OwnershipTransferPendingEvent {
+            owner: Some(Address::from(ACC_ADDR_OWNER)),
+            pending_owner: Some(Address::from(OTHER_ACCOUNT)),
         },
-        "OwnershipTransferredEvent event is wrong"
+        "OwnershipTransferPendingEvent event is wrong"
     );
 
-    // Checking `owner`.
+    // Checking that the ownership is not transferred before it is accepted.
 
-    let invoke = chain
-        .contract_invoke(
+    assert_eq!(
+        get_owner(&chain, initialization_registry.contract_address),
+        Some(Address::from(ACC_ADDR_OWNER))
+    );
+    assert_eq!(
+        get_pending_owner(&chain, initialization_registry.contract_address),
+        Some(Address::from(OTHER_ACCOUNT))
+    );
+
+    // Checking that only the pending owner can accept the ownership.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
             ACC_ADDR_OWNER,
             Address::Account(ACC_ADDR_OWNER),
             Energy::from(10000),
             UpdateContractPayload {
-                amount: Amount::zero(),
                 address: initialization_registry.contract_address,
-                receive_name: OwnedReceiveName::new_unchecked("registry.owner".to_string()),
+                receive_name: OwnedReceiveName::new_unchecked("registry.acceptOwnership".into()),
                 message: OwnedParameter::empty(),
+                amount: Amount::from_ccd(0),
             },
         )
-        .expect("Should be able to query owner address");
+        .expect_err("Should fail to acceptOwnership from an account that is not the pending owner");
 
-    let owner: Option<Address> =
-        from_bytes(&invoke.return_value).expect("Should return a valid result");
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Invoking 'acceptOwnership'.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            OTHER_ACCOUNT,
+            Address::Account(OTHER_ACCOUNT),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.acceptOwnership".into()),
+                message: OwnedParameter::empty(),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect("Should be able to acceptOwnership");
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 1, "Event tag is wrong");
 
-    assert_eq!(owner, Some(Address::from(OTHER_ACCOUNT)));
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: OwnershipTransferredEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        OwnershipTransferredEvent {
+            new_owner: Some(Address::from(OTHER_ACCOUNT)),
+            previous_owner: Some(Address::from(ACC_ADDR_OWNER)),
+        },
+        "OwnershipTransferredEvent event is wrong"
+    );
+
+    // Checking `owner` and `pendingOwner`.
+
+    assert_eq!(
+        get_owner(&chain, initialization_registry.contract_address),
+        Some(Address::from(OTHER_ACCOUNT))
+    );
+    assert_eq!(
+        get_pending_owner(&chain, initialization_registry.contract_address),
+        None
+    );
 
     // Invoking 'renounceOwnership'.
 
@@ -416,3 +649,2554 @@ fn test_owner_functionalities() {
 
     assert_eq!(owner, None);
 }
+
+/// Test cancelling an ownership transfer.
+#[test]
+fn test_cancel_ownership_transfer() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    // Checking that there is no ownership transfer to cancel.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "registry.cancelOwnershipTransfer".into(),
+                ),
+                message: OwnedParameter::empty(),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect_err("Should fail to cancelOwnershipTransfer without a pending owner");
+
+    assert_eq!(reject_reason(&update), Some(-8));
+
+    // Invoking 'transferOwnership'.
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.transferOwnership".into()),
+                message: OwnedParameter::from_serial(&Address::from(OTHER_ACCOUNT))
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect("Should be able to transferOwnership");
+
+    // Checking that only the owner can cancel the ownership transfer.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            OTHER_ACCOUNT,
+            Address::Account(OTHER_ACCOUNT),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "registry.cancelOwnershipTransfer".into(),
+                ),
+                message: OwnedParameter::empty(),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect_err("Should fail to cancelOwnershipTransfer from an account that is not the owner");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Invoking 'cancelOwnershipTransfer'.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "registry.cancelOwnershipTransfer".into(),
+                ),
+                message: OwnedParameter::empty(),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect("Should be able to cancelOwnershipTransfer");
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 2, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: OwnershipTransferPendingEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        OwnershipTransferPendingEvent {
+            owner: Some(Address::from(ACC_ADDR_OWNER)),
+            pending_owner: None,
+        },
+        "OwnershipTransferPendingEvent event is wrong"
+    );
+
+    // Checking that the cancelled ownership transfer can not be accepted.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            OTHER_ACCOUNT,
+            Address::Account(OTHER_ACCOUNT),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.acceptOwnership".into()),
+                message: OwnedParameter::empty(),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect_err("Should fail to acceptOwnership after the transfer got cancelled");
+
+    assert_eq!(reject_reason(&update), Some(-8));
+
+    assert_eq!(
+        get_owner(&chain, initialization_registry.contract_address),
+        Some(Address::from(ACC_ADDR_OWNER))
+    );
+}
+
+/// Test role-based access control.
+#[test]
+fn test_roles() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let registry = initialization_registry.contract_address;
+
+    let import_parameter = ImportAddressesParams {
+        entries: vec![ImportAddressesParam {
+            name: String::from("Contract1"),
+            destination: ContractAddress {
+                index: 8,
+                subindex: 0,
+            },
+        }],
+    };
+
+    // Checking that an account without the `Importer` role can not import addresses.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            OTHER_ACCOUNT,
+            Address::Account(OTHER_ACCOUNT),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.importAddresses".into()),
+                message: OwnedParameter::from_serial(&import_parameter)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect_err("Should fail to importAddresses without the `Importer` role");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Granting the `Importer` role to OTHER_ACCOUNT.
+
+    let input_parameter = RoleParams {
+        address: Address::Account(OTHER_ACCOUNT),
+        role: Role::Importer,
+    };
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.grantRole".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect("Should be able to grantRole");
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 3, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: RoleGrantedEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        RoleGrantedEvent {
+            address: Address::Account(OTHER_ACCOUNT),
+            role: Role::Importer,
+            sender: Address::Account(ACC_ADDR_OWNER),
+        },
+        "RoleGranted event is wrong"
+    );
+
+    assert!(has_role(&chain, registry, OTHER_ACCOUNT, Role::Importer));
+    assert!(!has_role(&chain, registry, OTHER_ACCOUNT, Role::Upgrader));
+
+    // Checking that the importer can import addresses.
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            OTHER_ACCOUNT,
+            Address::Account(OTHER_ACCOUNT),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.importAddresses".into()),
+                message: OwnedParameter::from_serial(&import_parameter)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect("Should be able to importAddresses with the `Importer` role");
+
+    // Checking that the importer can not upgrade contracts.
+
+    let input_parameter = AtomicUpdateParam {
+        module: ModuleReference::from([0u8; 32]),
+        migrate: None,
+        contract_address: ContractAddress {
+            index: 8,
+            subindex: 0,
+        },
+    };
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            OTHER_ACCOUNT,
+            Address::Account(OTHER_ACCOUNT),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.atomicUpdate".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect_err("Should fail to atomicUpdate without the `Upgrader` role");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Checking that the importer can not grant roles.
+
+    let input_parameter = RoleParams {
+        address: Address::Account(OTHER_ACCOUNT),
+        role: Role::Upgrader,
+    };
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            OTHER_ACCOUNT,
+            Address::Account(OTHER_ACCOUNT),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.grantRole".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect_err("Should fail to grantRole without the `Admin` role");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Checking that an admin can not grant the `Guardian` role to itself.
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "grantRole",
+        &RoleParams {
+            address: Address::Account(ACC_ADDR_OWNER),
+            role: Role::Guardian,
+        },
+    )
+    .expect_err("Should fail to grant the `Guardian` role to oneself");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Granting the `Guardian` role to GUARDIAN_ACCOUNT.
+
+    let input_parameter = RoleParams {
+        address: Address::Account(GUARDIAN_ACCOUNT),
+        role: Role::Guardian,
+    };
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.grantRole".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect("Should be able to grantRole");
+
+    // Checking that the guardian can not revoke the `Admin` role.
+
+    let input_parameter = RoleParams {
+        address: Address::Account(ACC_ADDR_OWNER),
+        role: Role::Admin,
+    };
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            GUARDIAN_ACCOUNT,
+            Address::Account(GUARDIAN_ACCOUNT),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.revokeRole".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect_err("Should fail to revoke the `Admin` role as guardian");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Revoking the `Importer` role from OTHER_ACCOUNT as guardian.
+
+    let input_parameter = RoleParams {
+        address: Address::Account(OTHER_ACCOUNT),
+        role: Role::Importer,
+    };
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            GUARDIAN_ACCOUNT,
+            Address::Account(GUARDIAN_ACCOUNT),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.revokeRole".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect("Should be able to revoke the `Importer` role as guardian");
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 4, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: RoleRevokedEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        RoleRevokedEvent {
+            address: Address::Account(OTHER_ACCOUNT),
+            role: Role::Importer,
+            sender: Address::Account(GUARDIAN_ACCOUNT),
+        },
+        "RoleRevoked event is wrong"
+    );
+
+    assert!(!has_role(&chain, registry, OTHER_ACCOUNT, Role::Importer));
+
+    // The owner has all roles except `Guardian`.
+    assert!(has_role(&chain, registry, ACC_ADDR_OWNER, Role::Admin));
+    assert!(has_role(&chain, registry, ACC_ADDR_OWNER, Role::Upgrader));
+    assert!(!has_role(&chain, registry, ACC_ADDR_OWNER, Role::Guardian));
+}
+
+/// Test the multisig proposals.
+#[test]
+fn test_multisig() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let registry = initialization_registry.contract_address;
+
+    let contract = ContractAddress {
+        index: 8,
+        subindex: 0,
+    };
+
+    let operation = Operation::ImportAddresses(ImportAddressesParams {
+        entries: vec![ImportAddressesParam {
+            name: String::from("Contract1"),
+            destination: contract,
+        }],
+    });
+
+    // Checking that proposing fails before the multisig is configured.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.propose".into()),
+                message: OwnedParameter::from_serial(&operation)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect_err("Should fail to propose without being a signer");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Checking that an invalid multisig configuration is rejected.
+
+    let invalid_parameters = [
+        (
+            MultisigParams {
+                signers: vec![Address::Account(ACC_ADDR_OWNER)],
+                threshold: 2,
+                proposal_duration: Duration::from_seconds(3600),
+            },
+            -12,
+        ),
+        (
+            MultisigParams {
+                signers: vec![
+                    Address::Account(ACC_ADDR_OWNER),
+                    Address::Account(ACC_ADDR_OWNER),
+                ],
+                threshold: 2,
+                proposal_duration: Duration::from_seconds(3600),
+            },
+            -13,
+        ),
+        (
+            MultisigParams {
+                signers: (0..256)
+                    .map(|index| Address::Contract(ContractAddress { index, subindex: 0 }))
+                    .collect(),
+                threshold: 1,
+                proposal_duration: Duration::from_seconds(3600),
+            },
+            -25,
+        ),
+    ];
+
+    for (input_parameter, reject_code) in invalid_parameters {
+        let update = chain
+            .contract_update(
+                Signer::with_one_key(),
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(10000),
+                UpdateContractPayload {
+                    address: registry,
+                    receive_name: OwnedReceiveName::new_unchecked("registry.setMultisig".into()),
+                    message: OwnedParameter::from_serial(&input_parameter)
+                        .expect("`input_parameter` should be a valid inut parameter"),
+                    amount: Amount::from_ccd(0),
+                },
+            )
+            .expect_err("Should fail to setMultisig with an invalid configuration");
+
+        assert_eq!(reject_reason(&update), Some(reject_code));
+    }
+
+    // Configuring a 2-of-3 multisig.
+
+    let input_parameter = MultisigParams {
+        signers: vec![
+            Address::Account(ACC_ADDR_OWNER),
+            Address::Account(OTHER_ACCOUNT),
+            Address::Account(GUARDIAN_ACCOUNT),
+        ],
+        threshold: 2,
+        proposal_duration: Duration::from_seconds(3600),
+    };
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.setMultisig".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect("Should be able to setMultisig");
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 5, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: MultisigUpdatedEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        MultisigUpdatedEvent {
+            signers: input_parameter.signers,
+            threshold: 2,
+            proposal_duration: Duration::from_seconds(3600),
+        },
+        "MultisigUpdated event is wrong"
+    );
+
+    // Checking that the owner lost its implicit roles and its single-key powers.
+
+    assert!(!has_role(&chain, registry, ACC_ADDR_OWNER, Role::Admin));
+    assert!(!has_role(&chain, registry, ACC_ADDR_OWNER, Role::Importer));
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "setMultisig",
+        &MultisigParams {
+            signers: vec![Address::Account(ACC_ADDR_OWNER)],
+            threshold: 1,
+            proposal_duration: Duration::from_seconds(3600),
+        },
+    )
+    .expect_err("Should fail to setMultisig once a multisig is configured");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "transferOwnership",
+        &Address::Account(OTHER_ACCOUNT),
+    )
+    .expect_err("Should fail to transferOwnership once a multisig is configured");
+
+    assert_eq!(reject_reason(&update), Some(-27));
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.renounceOwnership".into()),
+                message: OwnedParameter::empty(),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect_err("Should fail to renounceOwnership once a multisig is configured");
+
+    assert_eq!(reject_reason(&update), Some(-27));
+
+    // Proposing to import an address.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.propose".into()),
+                message: OwnedParameter::from_serial(&operation)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect("Should be able to propose");
+
+    let proposal_id: u64 = from_bytes(&update.return_value).expect("Should return a valid result");
+
+    assert_eq!(proposal_id, 0);
+
+    // Checking logged events.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+
+    assert_eq!(events[0].1[0].as_ref()[0], 6, "Event tag is wrong");
+
+    let event_struct: ProposalCreatedEvent =
+        from_bytes(&events[0].1[0].as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        ProposalCreatedEvent {
+            proposal_id: 0,
+            proposer: Address::Account(ACC_ADDR_OWNER),
+            expiry: Timestamp::from_timestamp_millis(3_600_000),
+        },
+        "ProposalCreated event is wrong"
+    );
+
+    assert_eq!(events[0].1[1].as_ref()[0], 7, "Event tag is wrong");
+
+    let event_struct: ProposalApprovedEvent =
+        from_bytes(&events[0].1[1].as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        ProposalApprovedEvent {
+            proposal_id: 0,
+            signer: Address::Account(ACC_ADDR_OWNER),
+            approvals: 1,
+        },
+        "ProposalApproved event is wrong"
+    );
+
+    // Checking that the operation is not executed before the threshold is reached.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.getAddress".to_string()),
+                message: OwnedParameter::from_serial(&String::from("Contract1"))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to query a contract address that is not registered");
+
+    assert_eq!(reject_reason(&invoke), Some(-4));
+
+    // Checking that a signer can not approve twice.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.approve".into()),
+                message: OwnedParameter::from_serial(&0u64)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect_err("Should fail to approve twice");
+
+    assert_eq!(reject_reason(&update), Some(-11));
+
+    // Approving the proposal by a second signer executes it.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            OTHER_ACCOUNT,
+            Address::Account(OTHER_ACCOUNT),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.approve".into()),
+                message: OwnedParameter::from_serial(&0u64)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect("Should be able to approve");
+
+    // Checking logged events.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+
+    let tags: Vec<u8> = events[0].1.iter().map(|event| event.as_ref()[0]).collect();
+
+    assert_eq!(tags, vec![7, 0, 8], "Event tags are wrong");
+
+    let event_struct: ProposalExecutedEvent =
+        from_bytes(&events[0].1[2].as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        ProposalExecutedEvent { proposal_id: 0 },
+        "ProposalExecuted event is wrong"
+    );
+
+    // Checking that the contract address was registered and the proposal removed.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.getAddress".to_string()),
+                message: OwnedParameter::from_serial(&String::from("Contract1"))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query contract address");
+
+    let contract_address: ContractAddress =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(contract_address, contract);
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.getProposal".to_string()),
+                message: OwnedParameter::from_serial(&0u64)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query the proposal");
+
+    let proposal: Option<Proposal> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert!(proposal.is_none());
+
+    // Checking that an executed proposal can not be approved.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            GUARDIAN_ACCOUNT,
+            Address::Account(GUARDIAN_ACCOUNT),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.approve".into()),
+                message: OwnedParameter::from_serial(&0u64)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect_err("Should fail to approve an executed proposal");
+
+    assert_eq!(reject_reason(&update), Some(-9));
+
+    // Proposing an ownership transfer that expires.
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.propose".into()),
+                message: OwnedParameter::from_serial(&Operation::TransferOwnership(
+                    Address::Account(OTHER_ACCOUNT),
+                ))
+                .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect("Should be able to propose");
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "removeExpiredProposals",
+        &vec![1u64],
+    )
+    .expect_err("Should fail to remove a proposal that has not expired");
+
+    assert_eq!(reject_reason(&update), Some(-26));
+
+    chain
+        .tick_block_time(Duration::from_seconds(3601))
+        .expect("Should be able to advance the block time");
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            OTHER_ACCOUNT,
+            Address::Account(OTHER_ACCOUNT),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.approve".into()),
+                message: OwnedParameter::from_serial(&1u64)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect_err("Should fail to approve an expired proposal");
+
+    assert_eq!(reject_reason(&update), Some(-10));
+
+    assert_eq!(get_pending_owner(&chain, registry), None);
+
+    // Removing the expired proposal.
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "removeExpiredProposals",
+        &vec![1u64],
+    )
+    .expect("Should be able to remove an expired proposal");
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 18, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: ProposalRemovedEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        ProposalRemovedEvent { proposal_id: 1 },
+        "ProposalRemoved event is wrong"
+    );
+
+    let proposal: Option<Proposal> = view_registry(&chain, registry, "getProposal", &1u64);
+
+    assert!(proposal.is_none());
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "removeExpiredProposals",
+        &vec![1u64],
+    )
+    .expect_err("Should fail to remove a proposal that does not exist");
+
+    assert_eq!(reject_reason(&update), Some(-9));
+
+    // Granting a role via the multisig.
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "propose",
+        &Operation::GrantRole(RoleParams {
+            address: Address::Account(OTHER_ACCOUNT),
+            role: Role::Importer,
+        }),
+    )
+    .expect("Should be able to propose");
+
+    let grant_role_proposal_id: u64 =
+        from_bytes(&update.return_value).expect("Should return a valid result");
+
+    let update = update_registry(
+        &mut chain,
+        GUARDIAN_ACCOUNT,
+        registry,
+        "approve",
+        &grant_role_proposal_id,
+    )
+    .expect("Should be able to approve");
+
+    // Checking logged events.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+
+    let tags: Vec<u8> = events[0].1.iter().map(|event| event.as_ref()[0]).collect();
+
+    assert_eq!(tags, vec![7, 3, 8], "Event tags are wrong");
+
+    assert!(has_role(&chain, registry, OTHER_ACCOUNT, Role::Importer));
+
+    // Changing the multisig via the multisig.
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "propose",
+        &Operation::SetMultisig(MultisigParams {
+            signers: vec![
+                Address::Account(ACC_ADDR_OWNER),
+                Address::Account(OTHER_ACCOUNT),
+            ],
+            threshold: 2,
+            proposal_duration: Duration::from_seconds(3600),
+        }),
+    )
+    .expect("Should be able to propose");
+
+    let set_multisig_proposal_id: u64 =
+        from_bytes(&update.return_value).expect("Should return a valid result");
+
+    let update = update_registry(
+        &mut chain,
+        GUARDIAN_ACCOUNT,
+        registry,
+        "approve",
+        &set_multisig_proposal_id,
+    )
+    .expect("Should be able to approve");
+
+    // Checking logged events.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+
+    let tags: Vec<u8> = events[0].1.iter().map(|event| event.as_ref()[0]).collect();
+
+    assert_eq!(tags, vec![7, 5, 8], "Event tags are wrong");
+
+    let update = update_registry(
+        &mut chain,
+        GUARDIAN_ACCOUNT,
+        registry,
+        "approve",
+        &grant_role_proposal_id,
+    )
+    .expect_err("Should fail to approve without being a signer anymore");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+}
+
+/// Test the timelocked operations.
+#[test]
+fn test_timelock() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let registry = initialization_registry.contract_address;
+
+    let contract = ContractAddress {
+        index: 8,
+        subindex: 0,
+    };
+
+    let operation = Operation::ImportAddresses(ImportAddressesParams {
+        entries: vec![ImportAddressesParam {
+            name: String::from("Contract1"),
+            destination: contract,
+        }],
+    });
+
+    let min_delay = Duration::from_seconds(3600);
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "grantRole",
+        &RoleParams {
+            address: Address::Account(OTHER_ACCOUNT),
+            role: Role::Importer,
+        },
+    )
+    .expect("Should be able to grantRole");
+
+    // Checking that only an admin can set the minimum delay.
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "setMinDelay",
+        &min_delay,
+    )
+    .expect_err("Should fail to setMinDelay without the `Admin` role");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "setMinDelay",
+        &min_delay,
+    )
+    .expect("Should be able to setMinDelay");
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 9, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: MinDelayUpdatedEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        MinDelayUpdatedEvent { min_delay },
+        "MinDelayUpdated event is wrong"
+    );
+
+    // Checking that the operations have to be queued.
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "importAddresses",
+        &ImportAddressesParams {
+            entries: vec![ImportAddressesParam {
+                name: String::from("Contract1"),
+                destination: contract,
+            }],
+        },
+    )
+    .expect_err("Should fail to importAddresses while the timelock is active");
+
+    assert_eq!(reject_reason(&update), Some(-17));
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "queue",
+        &Operation::TransferOwnership(Address::Account(OTHER_ACCOUNT)),
+    )
+    .expect_err("Should fail to queue an operation that can not be timelocked");
+
+    assert_eq!(reject_reason(&update), Some(-14));
+
+    let update = update_registry(&mut chain, GUARDIAN_ACCOUNT, registry, "queue", &operation)
+        .expect_err("Should fail to queue without the `Importer` role");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Queueing the operation as an importer.
+
+    let update = update_registry(&mut chain, OTHER_ACCOUNT, registry, "queue", &operation)
+        .expect("Should be able to queue");
+
+    let operation_id: u64 = from_bytes(&update.return_value).expect("Should return a valid result");
+
+    assert_eq!(operation_id, 0);
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    assert_eq!(event.as_ref()[0], 10, "Event tag is wrong");
+
+    let event_struct: OperationQueuedEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        OperationQueuedEvent {
+            operation_id: 0,
+            sender: Address::Account(OTHER_ACCOUNT),
+            eta: Timestamp::from_timestamp_millis(3_600_000),
+        },
+        "OperationQueued event is wrong"
+    );
+
+    // Checking that the operation can not be executed before the eta.
+
+    let update = update_registry(&mut chain, GUARDIAN_ACCOUNT, registry, "execute", &0u64)
+        .expect_err("Should fail to execute before the eta");
+
+    assert_eq!(reject_reason(&update), Some(-16));
+
+    chain
+        .tick_block_time(min_delay)
+        .expect("Should be able to advance the block time");
+
+    // Anyone can execute the operation after the eta.
+
+    let update = update_registry(&mut chain, GUARDIAN_ACCOUNT, registry, "execute", &0u64)
+        .expect("Should be able to execute");
+
+    // Checking logged events.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+
+    let tags: Vec<u8> = events[0].1.iter().map(|event| event.as_ref()[0]).collect();
+
+    assert_eq!(tags, vec![0, 11], "Event tags are wrong");
+
+    let event_struct: OperationExecutedEvent =
+        from_bytes(&events[0].1[1].as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        OperationExecutedEvent { operation_id: 0 },
+        "OperationExecuted event is wrong"
+    );
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.getAddress".to_string()),
+                message: OwnedParameter::from_serial(&String::from("Contract1"))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query contract address");
+
+    let contract_address: ContractAddress =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(contract_address, contract);
+
+    let update = update_registry(&mut chain, GUARDIAN_ACCOUNT, registry, "execute", &0u64)
+        .expect_err("Should fail to execute an operation twice");
+
+    assert_eq!(reject_reason(&update), Some(-15));
+
+    // Cancelling a queued operation.
+
+    let _update = update_registry(&mut chain, OTHER_ACCOUNT, registry, "queue", &operation)
+        .expect("Should be able to queue");
+
+    let update = update_registry(&mut chain, GUARDIAN_ACCOUNT, registry, "cancel", &1u64)
+        .expect_err("Should fail to cancel without the `Guardian` role");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Checking that roles are granted via the timelock.
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "grantRole",
+        &RoleParams {
+            address: Address::Account(GUARDIAN_ACCOUNT),
+            role: Role::Guardian,
+        },
+    )
+    .expect_err("Should fail to grantRole while the timelock is active");
+
+    assert_eq!(reject_reason(&update), Some(-17));
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "queue",
+        &Operation::GrantRole(RoleParams {
+            address: Address::Account(GUARDIAN_ACCOUNT),
+            role: Role::Guardian,
+        }),
+    )
+    .expect("Should be able to queue");
+
+    let operation_id: u64 = from_bytes(&update.return_value).expect("Should return a valid result");
+
+    assert_eq!(operation_id, 2);
+
+    chain
+        .tick_block_time(min_delay)
+        .expect("Should be able to advance the block time");
+
+    let update = update_registry(&mut chain, OTHER_ACCOUNT, registry, "execute", &2u64)
+        .expect("Should be able to execute");
+
+    // Checking logged events.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+
+    let tags: Vec<u8> = events[0].1.iter().map(|event| event.as_ref()[0]).collect();
+
+    assert_eq!(tags, vec![3, 11], "Event tags are wrong");
+
+    let update = update_registry(&mut chain, GUARDIAN_ACCOUNT, registry, "cancel", &1u64)
+        .expect("Should be able to cancel");
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    assert_eq!(event.as_ref()[0], 12, "Event tag is wrong");
+
+    let event_struct: OperationCancelledEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        OperationCancelledEvent {
+            operation_id: 1,
+            sender: Address::Account(GUARDIAN_ACCOUNT),
+        },
+        "OperationCancelled event is wrong"
+    );
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "registry.getQueuedOperation".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&1u64)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query the queued operation");
+
+    let queued_operation: Option<QueuedOperation> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert!(queued_operation.is_none());
+
+    // Only a guardian can bypass the timelock.
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "emergencyExecute",
+        &operation,
+    )
+    .expect_err("Should fail to emergencyExecute without the `Guardian` role");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "emergencyExecute",
+        &operation,
+    )
+    .expect_err("Should fail to emergencyExecute as the owner without the `Guardian` role");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    let update = update_registry(
+        &mut chain,
+        GUARDIAN_ACCOUNT,
+        registry,
+        "emergencyExecute",
+        &operation,
+    )
+    .expect("Should be able to emergencyExecute");
+
+    // Checking logged events.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+
+    let tags: Vec<u8> = events[0].1.iter().map(|event| event.as_ref()[0]).collect();
+
+    assert_eq!(tags, vec![0, 13], "Event tags are wrong");
+
+    // Checking that the operations of admins can not bypass the timelock.
+
+    let update = update_registry(
+        &mut chain,
+        GUARDIAN_ACCOUNT,
+        registry,
+        "emergencyExecute",
+        &Operation::SetMinDelay(Duration::from_seconds(0)),
+    )
+    .expect_err("Should fail to emergencyExecute an operation of admins");
+
+    assert_eq!(reject_reason(&update), Some(-14));
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "setMinDelay",
+        &Duration::from_seconds(0),
+    )
+    .expect_err("Should fail to lower the minimum delay while the timelock is active");
+
+    assert_eq!(reject_reason(&update), Some(-17));
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "setMinDelay",
+        &Duration::from_seconds(7200),
+    )
+    .expect("Should be able to raise the minimum delay");
+
+    // A guardian can still revoke the `Importer` role directly.
+
+    update_registry(
+        &mut chain,
+        GUARDIAN_ACCOUNT,
+        registry,
+        "revokeRole",
+        &RoleParams {
+            address: Address::Account(OTHER_ACCOUNT),
+            role: Role::Importer,
+        },
+    )
+    .expect("Should be able to revokeRole as guardian");
+
+    assert!(!has_role(&chain, registry, OTHER_ACCOUNT, Role::Importer));
+
+    // Checking that the `Guardian` role can not be granted to oneself.
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "queue",
+        &Operation::GrantRole(RoleParams {
+            address: Address::Account(ACC_ADDR_OWNER),
+            role: Role::Guardian,
+        }),
+    )
+    .expect("Should be able to queue");
+
+    let operation_id: u64 = from_bytes(&update.return_value).expect("Should return a valid result");
+
+    chain
+        .tick_block_time(Duration::from_seconds(7200))
+        .expect("Should be able to advance the block time");
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "execute",
+        &operation_id,
+    )
+    .expect_err("Should fail to grant the `Guardian` role to oneself");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Disabling the timelock via the timelock.
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "queue",
+        &Operation::SetMinDelay(Duration::from_seconds(0)),
+    )
+    .expect("Should be able to queue");
+
+    let operation_id: u64 = from_bytes(&update.return_value).expect("Should return a valid result");
+
+    chain
+        .tick_block_time(Duration::from_seconds(7200))
+        .expect("Should be able to advance the block time");
+
+    update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "execute",
+        &operation_id,
+    )
+    .expect("Should be able to execute");
+
+    let min_delay: Duration = view_registry(&chain, registry, "minDelay", &());
+
+    assert_eq!(min_delay, Duration::from_seconds(0));
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "grantRole",
+        &RoleParams {
+            address: Address::Account(OTHER_ACCOUNT),
+            role: Role::Importer,
+        },
+    )
+    .expect("Should be able to grantRole once the timelock is disabled");
+}
+
+/// Test the enumeration of the registry entries.
+#[test]
+fn test_enumerate_entries() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let registry = initialization_registry.contract_address;
+
+    let entries: Vec<RegistryEntry> = (1..=3)
+        .map(|i| RegistryEntry {
+            name: format!("Contract{i}"),
+            destination: ContractAddress {
+                index: 10 + i,
+                subindex: 0,
+            },
+        })
+        .collect();
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "importAddresses",
+        &ImportAddressesParams {
+            entries: entries
+                .iter()
+                .map(|entry| ImportAddressesParam {
+                    name: entry.name.clone(),
+                    destination: entry.destination,
+                })
+                .collect(),
+        },
+    )
+    .expect("Should be able to importAddresses");
+
+    // Checking the pagination of `getAllEntries`.
+
+    let page: Vec<RegistryEntry> = view_registry(
+        &chain,
+        registry,
+        "getAllEntries",
+        &PaginationParams {
+            offset: 0,
+            limit: 2,
+        },
+    );
+
+    assert_eq!(page, entries[..2]);
+
+    let page: Vec<RegistryEntry> = view_registry(
+        &chain,
+        registry,
+        "getAllEntries",
+        &PaginationParams {
+            offset: 2,
+            limit: 2,
+        },
+    );
+
+    assert_eq!(page, entries[2..]);
+
+    let page: Vec<RegistryEntry> = view_registry(
+        &chain,
+        registry,
+        "getAllEntries",
+        &PaginationParams {
+            offset: 5,
+            limit: 2,
+        },
+    );
+
+    assert!(page.is_empty());
+
+    // Checking `getNames`.
+
+    let names: Vec<String> = view_registry(
+        &chain,
+        registry,
+        "getNames",
+        &PaginationParams {
+            offset: 1,
+            limit: 10,
+        },
+    );
+
+    assert_eq!(
+        names,
+        vec![String::from("Contract2"), String::from("Contract3")]
+    );
+
+    // Checking `getManyAddresses`.
+
+    let addresses: Vec<Option<ContractAddress>> = view_registry(
+        &chain,
+        registry,
+        "getManyAddresses",
+        &vec![String::from("Contract3"), String::from("Unknown")],
+    );
+
+    assert_eq!(addresses, vec![Some(entries[2].destination), None]);
+}
+
+/// Test the address history per key name.
+#[test]
+fn test_history() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let registry = initialization_registry.contract_address;
+
+    let old_contract = ContractAddress {
+        index: 8,
+        subindex: 0,
+    };
+
+    let new_contract = ContractAddress {
+        index: 9,
+        subindex: 0,
+    };
+
+    let import = |destination| ImportAddressesParams {
+        entries: vec![ImportAddressesParam {
+            name: String::from("Contract1"),
+            destination,
+        }],
+    };
+
+    chain
+        .tick_block_time(Duration::from_seconds(10))
+        .expect("Should be able to advance the block time");
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "importAddresses",
+        &import(old_contract),
+    )
+    .expect("Should be able to importAddresses");
+
+    chain
+        .tick_block_time(Duration::from_seconds(10))
+        .expect("Should be able to advance the block time");
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "importAddresses",
+        &import(new_contract),
+    )
+    .expect("Should be able to importAddresses");
+
+    // Checking that the event carries the previous address.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    let event_struct: LogRegisteredEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        LogRegisteredEvent {
+            name: String::from("Contract1"),
+            destination: new_contract,
+            previous: Some(old_contract),
+        },
+        "LogRegistered event is wrong"
+    );
+
+    // Checking the history.
+
+    let history: Vec<HistoryEntry> =
+        view_registry(&chain, registry, "getHistory", &String::from("Contract1"));
+
+    assert_eq!(
+        history,
+        vec![
+            HistoryEntry {
+                destination: Some(old_contract),
+                timestamp: Timestamp::from_timestamp_millis(10_000),
+                sender: Address::Account(ACC_ADDR_OWNER),
+            },
+            HistoryEntry {
+                destination: Some(new_contract),
+                timestamp: Timestamp::from_timestamp_millis(20_000),
+                sender: Address::Account(ACC_ADDR_OWNER),
+            },
+        ]
+    );
+
+    let history: Vec<HistoryEntry> =
+        view_registry(&chain, registry, "getHistory", &String::from("Unknown"));
+
+    assert!(history.is_empty());
+
+    // Checking the address at different points in time.
+
+    for (timestamp, expected) in [
+        (5_000, None),
+        (10_000, Some(old_contract)),
+        (15_000, Some(old_contract)),
+        (20_000, Some(new_contract)),
+        (25_000, Some(new_contract)),
+    ] {
+        let address: Option<ContractAddress> = view_registry(
+            &chain,
+            registry,
+            "getAddressAt",
+            &AddressAtParams {
+                name: String::from("Contract1"),
+                timestamp: Timestamp::from_timestamp_millis(timestamp),
+            },
+        );
+
+        assert_eq!(address, expected, "Wrong address at {timestamp}");
+    }
+}
+
+/// Test `removeAddresses` function.
+#[test]
+fn test_remove_addresses() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let registry = initialization_registry.contract_address;
+
+    let contract = ContractAddress {
+        index: 8,
+        subindex: 0,
+    };
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "importAddresses",
+        &ImportAddressesParams {
+            entries: vec![ImportAddressesParam {
+                name: String::from("Contract1"),
+                destination: contract,
+            }],
+        },
+    )
+    .expect("Should be able to importAddresses");
+
+    let params = |names: Vec<&str>, unregister| RemoveAddressesParams {
+        names: names.into_iter().map(String::from).collect(),
+        unregister,
+    };
+
+    // Checking that only an importer can remove addresses.
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "removeAddresses",
+        &params(vec!["Contract1"], false),
+    )
+    .expect_err("Should fail to removeAddresses without the `Importer` role");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "grantRole",
+        &RoleParams {
+            address: Address::Account(OTHER_ACCOUNT),
+            role: Role::Importer,
+        },
+    )
+    .expect("Should be able to grantRole");
+
+    // Checking that unknown names can not be removed.
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "removeAddresses",
+        &params(vec!["Contract1", "Unknown"], false),
+    )
+    .expect_err("Should fail to remove a name that is not registered");
+
+    assert_eq!(reject_reason(&update), Some(-4));
+
+    // Checking that a failing `unregister` hook reverts the removal (the contract does not exist).
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "removeAddresses",
+        &params(vec!["Contract1"], true),
+    )
+    .expect_err("Should fail to execute the `unregister` hook");
+
+    assert_eq!(reject_reason(&update), Some(-6));
+
+    // Removing the address as an importer.
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "removeAddresses",
+        &params(vec!["Contract1"], false),
+    )
+    .expect("Should be able to removeAddresses");
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 14, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: LogUnregisteredEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        LogUnregisteredEvent {
+            destination: contract,
+            name: String::from("Contract1"),
+        },
+        "LogUnregistered event is wrong"
+    );
+
+    // Checking that the name is not registered anymore.
+
+    let addresses: Vec<Option<ContractAddress>> = view_registry(
+        &chain,
+        registry,
+        "getManyAddresses",
+        &vec![String::from("Contract1")],
+    );
+
+    assert_eq!(addresses, vec![None]);
+
+    let history: Vec<HistoryEntry> =
+        view_registry(&chain, registry, "getHistory", &String::from("Contract1"));
+
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].destination, None);
+    assert_eq!(history[1].sender, Address::Account(OTHER_ACCOUNT));
+
+    // Checking that removals have to be queued while the timelock is active.
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "importAddresses",
+        &ImportAddressesParams {
+            entries: vec![ImportAddressesParam {
+                name: String::from("Contract1"),
+                destination: contract,
+            }],
+        },
+    )
+    .expect("Should be able to importAddresses");
+
+    let min_delay = Duration::from_seconds(3600);
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "setMinDelay",
+        &min_delay,
+    )
+    .expect("Should be able to setMinDelay");
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "removeAddresses",
+        &params(vec!["Contract1"], false),
+    )
+    .expect_err("Should fail to removeAddresses while the timelock is active");
+
+    assert_eq!(reject_reason(&update), Some(-17));
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "queue",
+        &Operation::RemoveAddresses(params(vec!["Contract1"], false)),
+    )
+    .expect("Should be able to queue");
+
+    let operation_id: u64 = from_bytes(&update.return_value).expect("Should return a valid result");
+
+    chain
+        .tick_block_time(min_delay)
+        .expect("Should be able to advance the block time");
+
+    let update = update_registry(
+        &mut chain,
+        GUARDIAN_ACCOUNT,
+        registry,
+        "execute",
+        &operation_id,
+    )
+    .expect("Should be able to execute");
+
+    // Checking logged events.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+
+    let tags: Vec<u8> = events[0].1.iter().map(|event| event.as_ref()[0]).collect();
+
+    assert_eq!(tags, vec![14, 11], "Event tags are wrong");
+
+    let addresses: Vec<Option<ContractAddress>> = view_registry(
+        &chain,
+        registry,
+        "getManyAddresses",
+        &vec![String::from("Contract1")],
+    );
+
+    assert_eq!(addresses, vec![None]);
+}
+
+/// Test the authorization of the `atomicUpdateMany` function.
+#[test]
+fn test_atomic_update_many() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let registry = initialization_registry.contract_address;
+
+    let input_parameter = AtomicUpdateManyParams {
+        entries: vec![AtomicUpdateParam {
+            module: ModuleReference::from([0u8; 32]),
+            migrate: None,
+            contract_address: ContractAddress {
+                index: 8,
+                subindex: 0,
+            },
+        }],
+    };
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "atomicUpdateMany",
+        &input_parameter,
+    )
+    .expect_err("Should fail to atomicUpdateMany without the `Upgrader` role");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Checking that the invoke of a contract that does not exist fails.
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "atomicUpdateMany",
+        &input_parameter,
+    )
+    .expect_err("Should fail to upgrade a contract that does not exist");
+
+    assert_eq!(reject_reason(&update), Some(-6));
+
+    // Checking that the operation has to be queued while the timelock is active.
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "setMinDelay",
+        &Duration::from_seconds(3600),
+    )
+    .expect("Should be able to setMinDelay");
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "atomicUpdateMany",
+        &input_parameter,
+    )
+    .expect_err("Should fail to atomicUpdateMany while the timelock is active");
+
+    assert_eq!(reject_reason(&update), Some(-17));
+}
+
+/// Test the module and contract name allowlist.
+#[test]
+fn test_allowlist() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let registry = initialization_registry.contract_address;
+
+    // Deploying 'dummy_contract' contract.
+
+    let deployment = chain
+        .module_deploy_v1(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            module_load_v1("../dummy-contract/dummy_contract.wasm.v1")
+                .expect("`dummy_contract.wasm.v1` module should be loaded"),
+        )
+        .expect("`dummy_contract.wasm.v1` deployment should always succeed");
+
+    let initialization_dummy_contract = chain
+        .contract_init(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Energy::from(10000),
+            InitContractPayload {
+                amount: Amount::zero(),
+                mod_ref: deployment.module_reference,
+                init_name: OwnedContractName::new_unchecked("init_dummy_contract".to_string()),
+                param: OwnedParameter::empty(),
+            },
+        )
+        .expect("Initialization of `dummy_contract` should always succeed");
+
+    let dummy_contract = initialization_dummy_contract.contract_address;
+
+    let allowlist = Allowlist {
+        modules: vec![deployment.module_reference],
+        contract_name: OwnedContractName::new_unchecked("init_dummy_contract".to_string()),
+    };
+
+    let import = |destination| ImportAddressesParams {
+        entries: vec![ImportAddressesParam {
+            name: String::from("MyName"),
+            destination,
+        }],
+    };
+
+    // Checking that only an admin can set an allowlist.
+
+    let input_parameter = SetAllowlistParams {
+        name: String::from("MyName"),
+        allowlist: Some(allowlist.clone()),
+    };
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "setAllowlist",
+        &input_parameter,
+    )
+    .expect_err("Should fail to setAllowlist without the `Admin` role");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "setAllowlist",
+        &input_parameter,
+    )
+    .expect("Should be able to setAllowlist");
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 15, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: AllowlistUpdatedEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        AllowlistUpdatedEvent {
+            name: String::from("MyName"),
+            allowlist: Some(allowlist.clone()),
+        },
+        "AllowlistUpdated event is wrong"
+    );
+
+    let stored: Option<Allowlist> =
+        view_registry(&chain, registry, "getAllowlist", &String::from("MyName"));
+
+    assert_eq!(stored, Some(allowlist.clone()));
+
+    // Checking that contracts of another module are rejected (the registry contract itself).
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "importAddresses",
+        &import(registry),
+    )
+    .expect_err("Should fail to import a contract of an unapproved module");
+
+    assert_eq!(reject_reason(&update), Some(-18));
+
+    // Checking that contracts with another contract name are rejected.
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "setAllowlist",
+        &SetAllowlistParams {
+            name: String::from("MyName"),
+            allowlist: Some(Allowlist {
+                modules: vec![deployment.module_reference],
+                contract_name: OwnedContractName::new_unchecked("init_other".to_string()),
+            }),
+        },
+    )
+    .expect("Should be able to setAllowlist");
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "importContracts",
+        &ImportContractsParam {
+            entries: vec![dummy_contract],
+        },
+    )
+    .expect_err("Should fail to import a contract with an unapproved contract name");
+
+    assert_eq!(reject_reason(&update), Some(-19));
+
+    // Checking that approved contracts can be imported.
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "setAllowlist",
+        &input_parameter,
+    )
+    .expect("Should be able to setAllowlist");
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "importContracts",
+        &ImportContractsParam {
+            entries: vec![dummy_contract],
+        },
+    )
+    .expect("Should be able to import an approved contract");
+
+    // Checking that names without an allowlist accept any contract.
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "setAllowlist",
+        &SetAllowlistParams {
+            name: String::from("MyName"),
+            allowlist: None,
+        },
+    )
+    .expect("Should be able to remove the allowlist");
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "importAddresses",
+        &import(registry),
+    )
+    .expect("Should be able to import any contract without an allowlist");
+}
+
+/// Test the `upgrade` function of the registry.
+#[test]
+fn test_upgrade() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let registry = initialization_registry.contract_address;
+
+    let contract = ContractAddress {
+        index: 8,
+        subindex: 0,
+    };
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "importAddresses",
+        &ImportAddressesParams {
+            entries: vec![ImportAddressesParam {
+                name: String::from("Contract1"),
+                destination: contract,
+            }],
+        },
+    )
+    .expect("Should be able to importAddresses");
+
+    let module = chain
+        .get_contract(registry)
+        .expect("The registry contract should exist")
+        .module_reference;
+
+    // Checking that only an upgrader can upgrade the registry.
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "upgrade",
+        &UpgradeParams {
+            module,
+            migrate: None,
+        },
+    )
+    .expect_err("Should fail to upgrade without the `Upgrader` role");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Checking that the upgrade fails if the module does not exist.
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "upgrade",
+        &UpgradeParams {
+            module: ModuleReference::from([9u8; 32]),
+            migrate: None,
+        },
+    )
+    .expect_err("Should fail to upgrade to a module that does not exist");
+
+    assert_eq!(reject_reason(&update), Some(-20));
+
+    // Checking that the upgrade fails if the module does not contain the registry contract.
+
+    let deployment = chain
+        .module_deploy_v1(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            module_load_v1("../dummy-contract/dummy_contract.wasm.v1")
+                .expect("`dummy_contract.wasm.v1` module should be loaded"),
+        )
+        .expect("`dummy_contract.wasm.v1` deployment should always succeed");
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "upgrade",
+        &UpgradeParams {
+            module: deployment.module_reference,
+            migrate: None,
+        },
+    )
+    .expect_err("Should fail to upgrade to a module without the registry contract");
+
+    assert_eq!(reject_reason(&update), Some(-21));
+
+    // Checking that a failing migration reverts the upgrade.
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "upgrade",
+        &UpgradeParams {
+            module,
+            migrate: Some((
+                OwnedEntrypointName::new_unchecked("migration".to_string()),
+                OwnedParameter::empty(),
+            )),
+        },
+    )
+    .expect_err("Should fail to invoke a migration function that does not exist");
+
+    assert_eq!(reject_reason(&update), Some(-6));
+
+    // Upgrading the registry.
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "upgrade",
+        &UpgradeParams {
+            module,
+            migrate: None,
+        },
+    )
+    .expect("Should be able to upgrade");
+
+    // Checking that the state is kept.
+
+    let addresses: Vec<Option<ContractAddress>> = view_registry(
+        &chain,
+        registry,
+        "getManyAddresses",
+        &vec![String::from("Contract1")],
+    );
+
+    assert_eq!(addresses, vec![Some(contract)]);
+
+    assert_eq!(
+        get_owner(&chain, registry),
+        Some(Address::Account(ACC_ADDR_OWNER))
+    );
+
+    // Checking that the upgrade has to go through the timelock while a minimum delay is set.
+
+    let min_delay = Duration::from_seconds(3600);
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "setMinDelay",
+        &min_delay,
+    )
+    .expect("Should be able to setMinDelay");
+
+    let params = UpgradeParams {
+        module,
+        migrate: None,
+    };
+
+    let update = update_registry(&mut chain, ACC_ADDR_OWNER, registry, "upgrade", &params)
+        .expect_err("Should fail to upgrade while the timelock is active");
+
+    assert_eq!(reject_reason(&update), Some(-17));
+
+    update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "queue",
+        &Operation::UpgradeRegistry(params.clone()),
+    )
+    .expect("Should be able to queue");
+
+    chain
+        .tick_block_time(min_delay)
+        .expect("Should be able to advance the block time");
+
+    update_registry(&mut chain, OTHER_ACCOUNT, registry, "execute", &0u64)
+        .expect("Should be able to execute");
+
+    let approved_upgrade: Option<UpgradeParams> =
+        view_registry(&chain, registry, "approvedUpgrade", &());
+
+    assert_eq!(approved_upgrade, Some(params.clone()));
+
+    // Checking that only the approved upgrade can be executed.
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "upgrade",
+        &UpgradeParams {
+            module,
+            migrate: Some((
+                OwnedEntrypointName::new_unchecked("migration".to_string()),
+                OwnedParameter::empty(),
+            )),
+        },
+    )
+    .expect_err("Should fail to execute an upgrade that was not approved");
+
+    assert_eq!(reject_reason(&update), Some(-17));
+
+    update_registry(&mut chain, ACC_ADDR_OWNER, registry, "upgrade", &params)
+        .expect("Should be able to execute the approved upgrade");
+
+    // Checking that the approval is consumed.
+
+    let approved_upgrade: Option<UpgradeParams> =
+        view_registry(&chain, registry, "approvedUpgrade", &());
+
+    assert_eq!(approved_upgrade, None);
+
+    let update = update_registry(&mut chain, ACC_ADDR_OWNER, registry, "upgrade", &params)
+        .expect_err("Should fail to execute the approved upgrade twice");
+
+    assert_eq!(reject_reason(&update), Some(-17));
+}
+
+/// Test the `forward` function of the registry.
+#[test]
+fn test_forward() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let registry = initialization_registry.contract_address;
+
+    register_dummy_contract(&mut chain, registry);
+
+    // Forwarding an invoke to the registered contract.
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "forward",
+        &ForwardParams {
+            name: String::from("MyName"),
+            entrypoint: OwnedEntrypointName::new_unchecked("getName".to_string()),
+            parameter: OwnedParameter::empty(),
+        },
+    )
+    .expect("Should be able to forward an invoke");
+
+    let name: String = from_bytes(&update.return_value).expect("Should return a valid result");
+
+    assert_eq!(name, String::from("MyName"));
+
+    // Checking that forwarding to a name that is not registered fails.
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "forward",
+        &ForwardParams {
+            name: String::from("NotRegistered"),
+            entrypoint: OwnedEntrypointName::new_unchecked("getName".to_string()),
+            parameter: OwnedParameter::empty(),
+        },
+    )
+    .expect_err("Should fail to forward to a name that is not registered");
+
+    assert_eq!(reject_reason(&update), Some(-4));
+
+    // Checking that a failing invoke of the registered contract fails.
+
+    let update = update_registry(
+        &mut chain,
+        OTHER_ACCOUNT,
+        registry,
+        "forward",
+        &ForwardParams {
+            name: String::from("MyName"),
+            entrypoint: OwnedEntrypointName::new_unchecked("notExisting".to_string()),
+            parameter: OwnedParameter::empty(),
+        },
+    )
+    .expect_err("Should fail to forward to an entry point that does not exist");
+
+    assert_eq!(reject_reason(&update), Some(-6));
+}
+
+/// Test that the registry hooks are not forwarded and that no CCD can be forwarded.
+#[test]
+fn test_forward_denied() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let registry = initialization_registry.contract_address;
+
+    register_dummy_contract(&mut chain, registry);
+
+    // Checking that the hooks that registered contracts only accept from the registry are not forwarded.
+
+    for hook in ["upgradeNatively", "unregister"] {
+        let update = update_registry(
+            &mut chain,
+            OTHER_ACCOUNT,
+            registry,
+            "forward",
+            &ForwardParams {
+                name: String::from("MyName"),
+                entrypoint: OwnedEntrypointName::new_unchecked(hook.to_string()),
+                parameter: OwnedParameter::empty(),
+            },
+        )
+        .expect_err("Should fail to forward a registry hook");
+
+        assert_eq!(reject_reason(&update), Some(-24));
+    }
+
+    // Checking that the `forward` entry point is not payable.
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            OTHER_ACCOUNT,
+            Address::Account(OTHER_ACCOUNT),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.forward".to_string()),
+                message: OwnedParameter::from_serial(&ForwardParams {
+                    name: String::from("MyName"),
+                    entrypoint: OwnedEntrypointName::new_unchecked("getName".to_string()),
+                    parameter: OwnedParameter::empty(),
+                })
+                .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(1),
+            },
+        )
+        .expect_err("Should fail to forward CCD");
+}
+
+/// Test the `ContractReplaced` event.
+#[test]
+fn test_contract_replaced_event() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let registry = initialization_registry.contract_address;
+
+    let old_contract = ContractAddress {
+        index: 8,
+        subindex: 0,
+    };
+
+    let new_contract = ContractAddress {
+        index: 9,
+        subindex: 0,
+    };
+
+    let import = |destination| ImportAddressesParams {
+        entries: vec![ImportAddressesParam {
+            name: String::from("Contract1"),
+            destination,
+        }],
+    };
+
+    // Registering a new name logs no `ContractReplaced` event.
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "importAddresses",
+        &import(old_contract),
+    )
+    .expect("Should be able to importAddresses");
+
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+
+    assert_eq!(events[0].1.len(), 1);
+
+    // Registering the same contract address again logs no `ContractReplaced` event.
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "importAddresses",
+        &import(old_contract),
+    )
+    .expect("Should be able to importAddresses");
+
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+
+    assert_eq!(events[0].1.len(), 1);
+
+    // Replacing the contract address logs the `ContractReplaced` event.
+
+    let update = update_registry(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        registry,
+        "importAddresses",
+        &import(new_contract),
+    )
+    .expect("Should be able to importAddresses");
+
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+
+    assert_eq!(events[0].1.len(), 2);
+
+    let event = &events[0].1[1];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 17, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: ContractReplacedEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        ContractReplacedEvent {
+            name: String::from("Contract1"),
+            old_address: old_contract,
+            new_address: new_contract,
+        },
+        "ContractReplaced event is wrong"
+    );
+}
+
+/// Test the access control of the `rollback` function.
+#[test]
+fn test_rollback() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let registry = initialization_registry.contract_address;
+
+    let contract = ContractAddress {
+        index: 8,
+        subindex: 0,
+    };
+
+    let rollback = RollbackParams {
+        contract_address: contract,
+        migrate: None,
+    };
+
+    // Checking that only an upgrader can roll back a contract.
+
+    let update = update_registry(&mut chain, OTHER_ACCOUNT, registry, "rollback", &rollback)
+        .expect_err("Should fail to roll back without the upgrader role");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Checking that a contract that was not upgraded via the registry can not be rolled back.
+
+    let update = update_registry(&mut chain, ACC_ADDR_OWNER, registry, "rollback", &rollback)
+        .expect_err("Should fail to roll back a contract that was not upgraded");
+
+    assert_eq!(reject_reason(&update), Some(-23));
+
+    let previous_modules: Vec<ModuleReference> =
+        view_registry(&chain, registry, "getPreviousModules", &contract);
+
+    assert!(previous_modules.is_empty());
+}
@@ -1,43 +1,121 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 //! # Staking Bank
+//!
+//! The validators (public keys and URLs) are provided when initializing the contract, so the same module
+//! can be used for every environment (e.g. production, development, sandbox, local integration tests).
+//!
+//! The `UmbrellaFeeds` contract (looked up in the registry) reports the validators that signed a successful price update
+//! via the `reportParticipation` entry point. A validator is jailed if a report was received more than the liveness window
+//! after its last participation. A jailed validator fails the `verifyValidators` checks until it is unjailed, either by the
+//! owner (`unjail`) or by itself with a signature of its validator key (`selfUnjail`).
+//! Liveness is measured against the last received report instead of the current time, so validators are not jailed while
+//! no reports reach this contract (e.g. because the `UmbrellaFeeds` contract is not registered in the registry), and the
+//! signers of the last report are never jailed.
+//!
+//! Accounts can delegate CCD to a validator (`delegate`). The balance of a validator is `ONE` plus the CCD (in micro CCD)
+//! delegated to it. Undelegated CCD is locked for the unbonding period before it can be withdrawn (`withdraw`).
+//! ATTENTION: Code upgrades go through the registry (`upgradeNatively`) and keep the contract address and the delegations.
+//! The validators are provided at initialization, so changing the list of validators still requires a new staking bank
+//! contract. Delegators then have to undelegate and withdraw their CCD from the old contract and delegate it to the new one.
+//!
+//! The delegated stake is exposed as a CIS-2 token. Every validator is a token with its public key as token ID, and the
+//! balance of an account is the CCD (in micro CCD) it delegated to the validator. `delegate` and `undelegate` log the
+//! CIS-2 `Mint` and `Burn` events, and the `TokenMetadata` event of every validator is logged at initialization. The token
+//! is non-transferable: `transfer` and `updateOperator` always reject and `operatorOf` always returns false.
+//!
+//! ATTENTION: Keep the `upgradeNatively`/`unregister` entry points in this contract at all times and make sure their logic can be
+//! executed successfully via an invoke to the `atomicUpdate` entry point in the `registry` contract. Otherwise, you will not be able to
+//! natively upgrade this contract via the `registry` contract anymore.
+use concordium_cis2::{
+    BalanceOfQueryParams, BalanceOfQueryResponse, BurnEvent, Cis2Error, Cis2Event, MintEvent,
+    OperatorOfQueryParams, OperatorOfQueryResponse, StandardIdentifier, SupportResult,
+    SupportsQueryParams, SupportsQueryResponse, TokenAmountU64, TokenIdFixed, TokenMetadataEvent,
+    TokenMetadataQueryParams, TokenMetadataQueryResponse, TransferParams, UpdateOperatorParams,
+    CIS0_STANDARD_IDENTIFIER, CIS2_STANDARD_IDENTIFIER,
+};
 use concordium_std::*;
 use core::fmt::Debug;
 
-#[cfg(feature = "production")]
-mod production_constants;
-#[cfg(feature = "production")]
-use production_constants::*;
-
-#[cfg(feature = "development")]
-mod development_constants;
-#[cfg(feature = "development")]
-use development_constants::*;
-
-#[cfg(feature = "sandbox")]
-mod sandbox_constants;
-#[cfg(feature = "sandbox")]
-use sandbox_constants::*;
-
-// To run local integration test cases.
-#[cfg(feature = "local")]
-mod local_constants;
-#[cfg(feature = "local")]
-use local_constants::*;
-
-#[cfg(any(
-    feature = "production",
-    feature = "development",
-    feature = "sandbox",
-    feature = "local"
-))]
-const ONE: StakingBalanceAmount = 1u8;
-
-#[allow(dead_code)]
-type StakingBalanceAmount = u8;
-
-#[derive(Serial, Deserial)]
-pub struct State {}
+/// The balance of a validator without any delegated CCD (1 CCD in micro CCD).
+const ONE: StakingBalanceAmount = 1_000_000u64;
+
+/// The balance type of the validators (in micro CCD).
+pub type StakingBalanceAmount = u64;
+
+/// The token ID of the CIS-2 token of a validator (the bytes of its public key).
+pub type ContractTokenId = TokenIdFixed<32>;
+
+/// The token amount of the CIS-2 token (the delegated CCD in micro CCD).
+pub type ContractTokenAmount = TokenAmountU64;
+
+/// The parameter type for the CIS-2 function `balanceOf`.
+pub type ContractBalanceOfQueryParams = BalanceOfQueryParams<ContractTokenId>;
+
+/// The response type for the CIS-2 function `balanceOf`.
+pub type ContractBalanceOfQueryResponse = BalanceOfQueryResponse<ContractTokenAmount>;
+
+/// The parameter type for the CIS-2 function `tokenMetadata`.
+pub type ContractTokenMetadataQueryParams = TokenMetadataQueryParams<ContractTokenId>;
+
+/// The parameter type for the CIS-2 function `transfer`.
+pub type ContractTransferParams = TransferParams<ContractTokenId, ContractTokenAmount>;
+
+/// The standards supported by this contract.
+const SUPPORTS_STANDARDS: [StandardIdentifier<'static>; 2] =
+    [CIS0_STANDARD_IDENTIFIER, CIS2_STANDARD_IDENTIFIER];
+
+/// The information stored about a validator.
+#[derive(Serialize, Clone, Debug)]
+struct ValidatorInfo {
+    /// The URL of the validator.
+    url: String,
+    /// The last time the validator participated in a successful price update (or was unjailed).
+    last_participation: Timestamp,
+    /// The total amount of CCD delegated to the validator.
+    delegated_stake: Amount,
+}
+
+impl ValidatorInfo {
+    /// Internal function that returns the balance of the validator (`ONE` plus the delegated CCD in micro CCD).
+    fn balance(&self) -> StakingBalanceAmount {
+        ONE.saturating_add(self.delegated_stake.micro_ccd)
+    }
+}
+
+/// CCD of a delegator that is unbonding (one entry per release time).
+#[derive(Debug, Serialize, SchemaType, Clone, Copy, PartialEq, Eq)]
+pub struct Unbonding {
+    /// The amount of CCD that is unbonding.
+    pub amount: Amount,
+    /// The time from which the CCD can be withdrawn.
+    pub release_time: Timestamp,
+}
+
+#[derive(Serial, DeserialWithState)]
+#[concordium(state_parameter = "S")]
+pub struct State<S = StateApi> {
+    /// The owner of this contract (the account that initialized it). It can unjail validators and set the liveness window.
+    owner: Address,
+    /// Registry contract where the `UmbrellaFeeds` contract that reports the participation of validators is looked up.
+    registry: ContractAddress,
+    /// The duration a validator can be inactive before it is jailed.
+    liveness_window: Duration,
+    /// The public keys of all validators in the order they were provided at initialization.
+    public_keys: Vec<PublicKeyEd25519>,
+    /// Map from the public key of a validator to its URL, last participation and delegated stake.
+    validators: StateMap<PublicKeyEd25519, ValidatorInfo, S>,
+    /// The duration undelegated CCD is locked before it can be withdrawn.
+    unbonding_period: Duration,
+    /// The total amount of CCD delegated to all validators.
+    total_delegated_stake: Amount,
+    /// Map from a delegator and a validator to the amount of CCD the delegator delegated to the validator.
+    delegations: StateMap<(AccountAddress, PublicKeyEd25519), Amount, S>,
+    /// Map from a delegator to its CCD that is unbonding (ordered by release time, oldest first).
+    unbondings: StateMap<AccountAddress, Vec<Unbonding>, S>,
+    /// The last time the participation of validators was reported via `reportParticipation` (or the initialization time).
+    last_report: Timestamp,
+}
 
 /// All smart contract errors.
 #[derive(Debug, PartialEq, Eq, Reject, Serial, SchemaType)]
@@ -52,10 +130,56 @@ enum CustomContractError {
     /// Failed to invoke a contract.
     InvokeContractError, // -4
     /// Failed because the address is not a validator.
-    #[allow(dead_code)]
     NotValidator, // -5
+    /// Failed because the number of validators is not between 1 and 255.
+    InvalidNumberOfValidators, // -6
+    /// Failed because a public key is provided more than once.
+    DuplicateValidator, // -7
+    /// Failed because the index is out of bounds of the validators list.
+    IndexOutOfBounds, // -8
+    /// Failed because of unauthorized invoke of the entry point.
+    Unauthorized, // -9
+    /// Failed because the liveness window is zero.
+    InvalidLivenessWindow, // -10
+    /// Failed because it is the wrong contract.
+    WrongContract, // -11
+    /// Failed because the signature is outdated.
+    Expired, // -12
+    /// Failed signature verification because of an invalid signature.
+    WrongSignature, // -13
+    /// Failed because only accounts can delegate.
+    OnlyAccount, // -14
+    /// Failed because the amount is zero.
+    InvalidAmount, // -15
+    /// Failed because the delegator has not delegated enough CCD to the validator.
+    InsufficientDelegation, // -16
+    /// Failed because the delegator has no CCD that can be withdrawn (yet).
+    NothingToWithdraw, // -17
+    /// Failed to transfer CCD to the delegator.
+    InvokeTransferError, // -18
+    /// Failed because of an overflow.
+    Overflow, // -19
+    /// Upgrade failed because the new module does not exist.
+    FailedUpgradeMissingModule, // -20
+    /// Upgrade failed because the new module does not contain a contract with a
+    /// matching name.
+    FailedUpgradeMissingContract, // -21
+    /// Upgrade failed because the smart contract version of the module is not
+    /// supported.
+    FailedUpgradeUnsupportedModuleVersion, // -22
+    /// Failed because the unjail message does not match the last participation of the validator (e.g. the message was
+    /// already used).
+    OutdatedUnjailMessage, // -23
+    /// Failed because the validator is not jailed.
+    NotJailed, // -24
 }
 
+/// The error type of the CIS-2 functions, wrapping the CustomContractError.
+type ContractError = Cis2Error<CustomContractError>;
+
+/// The result type of the CIS-2 functions.
+type ContractResult<A> = Result<A, ContractError>;
+
 /// Mapping errors related to logging to CustomContractError.
 impl From<LogError> for CustomContractError {
     fn from(le: LogError) -> Self {
@@ -66,6 +190,18 @@ impl From<LogError> for CustomContractError {
     }
 }
 
+/// Mapping errors related to contract upgrades to CustomContractError.
+impl From<UpgradeError> for CustomContractError {
+    #[inline(always)]
+    fn from(ue: UpgradeError) -> Self {
+        match ue {
+            UpgradeError::MissingModule => Self::FailedUpgradeMissingModule,
+            UpgradeError::MissingContract => Self::FailedUpgradeMissingContract,
+            UpgradeError::UnsupportedModuleVersion => Self::FailedUpgradeUnsupportedModuleVersion,
+        }
+    }
+}
+
 /// Mapping errors related to contract invocations to CustomContractError.
 impl<T> From<CallContractError<T>> for CustomContractError {
     fn from(_cce: CallContractError<T>) -> Self {
@@ -73,22 +209,210 @@ impl<T> From<CallContractError<T>> for CustomContractError {
     }
 }
 
+/// Tagged events to be serialized for the event log. The custom events use the tags from 0 upwards, outside of the tags
+/// 251 to 255 that are reserved for the CIS-2 events.
+#[derive(Debug, Serial, SchemaType)]
+#[concordium(repr(u8))]
+enum Event {
+    /// The event tracks whenever a validator gets unjailed.
+    #[concordium(tag = 0)]
+    Unjailed(UnjailedEvent),
+    /// The event tracks whenever the liveness window gets updated.
+    #[concordium(tag = 1)]
+    LivenessWindowUpdated(LivenessWindowUpdatedEvent),
+    /// The event tracks whenever CCD gets delegated to a validator.
+    #[concordium(tag = 2)]
+    Delegated(DelegatedEvent),
+    /// The event tracks whenever CCD gets undelegated from a validator.
+    #[concordium(tag = 3)]
+    Undelegated(UndelegatedEvent),
+    /// The event tracks whenever unbonded CCD gets withdrawn.
+    #[concordium(tag = 4)]
+    Withdrawn(WithdrawnEvent),
+    /// The CIS-2 events (`Mint` on delegation, `Burn` on undelegation and `TokenMetadata` at initialization).
+    #[concordium(forward = cis2_events)]
+    Cis2Event(Cis2Event<ContractTokenId, ContractTokenAmount>),
+}
+
+/// The UnjailedEvent is logged when a validator gets unjailed.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct UnjailedEvent {
+    /// The public key of the validator.
+    pub public_key: PublicKeyEd25519,
+    /// The address that unjailed the validator (the owner or the invoker of `selfUnjail`).
+    pub unjailed_by: Address,
+}
+
+/// The LivenessWindowUpdatedEvent is logged when the liveness window gets updated.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct LivenessWindowUpdatedEvent {
+    /// The previous liveness window.
+    pub previous_liveness_window: Duration,
+    /// The new liveness window.
+    pub new_liveness_window: Duration,
+}
+
+/// The DelegatedEvent is logged when CCD gets delegated to a validator.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct DelegatedEvent {
+    /// The delegator.
+    pub delegator: AccountAddress,
+    /// The public key of the validator.
+    pub validator: PublicKeyEd25519,
+    /// The amount of CCD delegated.
+    pub amount: Amount,
+    /// The total amount of CCD the delegator has delegated to the validator afterwards.
+    pub delegation: Amount,
+}
+
+/// The UndelegatedEvent is logged when CCD gets undelegated from a validator.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct UndelegatedEvent {
+    /// The delegator.
+    pub delegator: AccountAddress,
+    /// The public key of the validator.
+    pub validator: PublicKeyEd25519,
+    /// The amount of CCD undelegated.
+    pub amount: Amount,
+    /// The total amount of CCD the delegator has delegated to the validator afterwards.
+    pub delegation: Amount,
+    /// The time from which the undelegated CCD can be withdrawn.
+    pub release_time: Timestamp,
+}
+
+/// The WithdrawnEvent is logged when unbonded CCD gets withdrawn.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct WithdrawnEvent {
+    /// The delegator.
+    pub delegator: AccountAddress,
+    /// The amount of CCD withdrawn.
+    pub amount: Amount,
+}
+
+impl<S: HasStateApi> State<S> {
+    /// Internal function that returns a boolean if the given public key is a validator that is not jailed.
+    fn is_active_validator(&self, validator: &PublicKeyEd25519) -> bool {
+        self.validators
+            .get(validator)
+            .is_some_and(|info| !self.is_inactive(&info))
+    }
+
+    /// Internal function that returns a boolean if the last report was received more than the liveness window after the
+    /// last participation of the validator.
+    fn is_inactive(&self, info: &ValidatorInfo) -> bool {
+        info.last_participation
+            .checked_add(self.liveness_window)
+            .is_some_and(|deadline| self.last_report > deadline)
+    }
+
+    /// Internal function that returns the number of validators.
+    fn number_of_validators(&self) -> u8 {
+        // The number of validators is checked to fit into a `u8` at initialization.
+        self.public_keys.len() as u8
+    }
+
+    /// Internal function that returns the balance of the given public key (zero if it is not a validator).
+    fn balance_of(&self, validator: &PublicKeyEd25519) -> StakingBalanceAmount {
+        self.validators
+            .get(validator)
+            .map_or(0, |info| info.balance())
+    }
+
+    /// Internal function that returns the total balance of all validators.
+    fn total_supply(&self) -> StakingBalanceAmount {
+        (u64::from(self.number_of_validators()) * ONE)
+            .saturating_add(self.total_delegated_stake.micro_ccd)
+    }
+}
+
+/// The parameter type for the contract init function.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct InitParamsStakingBank {
+    /// The public keys and URLs of the validators.
+    pub validators: Vec<(PublicKeyEd25519, String)>,
+    /// Registry contract where the `UmbrellaFeeds` contract is looked up.
+    pub registry: ContractAddress,
+    /// The duration a validator can be inactive before it is jailed.
+    pub liveness_window: Duration,
+    /// The duration undelegated CCD is locked before it can be withdrawn.
+    pub unbonding_period: Duration,
+}
+
 /// Init function that creates a new smart contract.
-#[init(contract = "staking_bank")]
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+/// - The number of validators is not between 1 and 255.
+/// - A public key is provided more than once.
+/// - The liveness window is zero.
+#[init(
+    contract = "staking_bank",
+    parameter = "InitParamsStakingBank",
+    event = "Event",
+    enable_logger
+)]
 fn init<S: HasStateApi>(
-    _ctx: &impl HasInitContext,
-    _state_builder: &mut StateBuilder<S>,
-) -> InitResult<State> {
-    Ok(State {})
+    ctx: &impl HasInitContext,
+    state_builder: &mut StateBuilder<S>,
+    logger: &mut impl HasLogger,
+) -> InitResult<State<S>> {
+    let param: InitParamsStakingBank = ctx.parameter_cursor().get()?;
+
+    ensure!(
+        !param.validators.is_empty() && param.validators.len() <= u8::MAX as usize,
+        CustomContractError::InvalidNumberOfValidators.into()
+    );
+
+    ensure!(
+        param.liveness_window.millis() > 0,
+        CustomContractError::InvalidLivenessWindow.into()
+    );
+
+    let now = ctx.metadata().slot_time();
+
+    let mut state = State {
+        owner: Address::from(ctx.init_origin()),
+        registry: param.registry,
+        liveness_window: param.liveness_window,
+        public_keys: Vec::with_capacity(param.validators.len()),
+        validators: state_builder.new_map(),
+        unbonding_period: param.unbonding_period,
+        total_delegated_stake: Amount::zero(),
+        delegations: state_builder.new_map(),
+        unbondings: state_builder.new_map(),
+        last_report: now,
+    };
+
+    for (key, url) in param.validators {
+        // Log the CIS-2 TokenMetadata event of the token of the validator
+        logger
+            .log(&Event::Cis2Event(Cis2Event::TokenMetadata(
+                TokenMetadataEvent {
+                    token_id: TokenIdFixed(key.0),
+                    metadata_url: MetadataUrl {
+                        url: url.clone(),
+                        hash: None,
+                    },
+                },
+            )))
+            .map_err(CustomContractError::from)?;
+
+        let info = ValidatorInfo {
+            url,
+            last_participation: now,
+            delegated_stake: Amount::zero(),
+        };
+        ensure!(
+            state.validators.insert(key, info).is_none(),
+            CustomContractError::DuplicateValidator.into()
+        );
+        state.public_keys.push(key);
+    }
+
+    Ok(state)
 }
 
 /// Equivalent to solidity's getter function which is automatically created from the public storage variable `NUMBER_OF_VALIDATORS`.
-#[cfg(any(
-    feature = "production",
-    feature = "development",
-    feature = "sandbox",
-    feature = "local"
-))]
 #[receive(
     contract = "staking_bank",
     name = "NUMBER_OF_VALIDATORS",
@@ -96,18 +420,12 @@ fn init<S: HasStateApi>(
 )]
 fn number_of_validators<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<u8> {
-    Ok(NUMBER_OF_VALIDATORS)
+    Ok(host.state().number_of_validators())
 }
 
 /// Equivalent to solidity's getter function which is automatically created from the public storage variable `TOTAL_SUPPLY`.
-#[cfg(any(
-    feature = "production",
-    feature = "development",
-    feature = "sandbox",
-    feature = "local"
-))]
 #[receive(
     contract = "staking_bank",
     name = "TOTAL_SUPPLY",
@@ -115,18 +433,13 @@ fn number_of_validators<S: HasStateApi>(
 )]
 fn total_supply_1<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<StakingBalanceAmount> {
-    Ok(TOTAL_SUPPLY)
+    // total supply = number_of_validators * ONE + total delegated stake.
+    Ok(host.state().total_supply())
 }
 
 /// Equivalent to solidity's getter function which is automatically created from the public storage variable `ONE`.
-#[cfg(any(
-    feature = "production",
-    feature = "development",
-    feature = "sandbox",
-    feature = "local"
-))]
 #[receive(
     contract = "staking_bank",
     name = "ONE",
@@ -134,18 +447,12 @@ fn total_supply_1<S: HasStateApi>(
 )]
 fn one<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<StakingBalanceAmount> {
     Ok(ONE)
 }
 
-/// View function that returns the balance of an validator.
-#[cfg(any(
-    feature = "production",
-    feature = "development",
-    feature = "sandbox",
-    feature = "local"
-))]
+/// View function that returns the balance of an validator (same as `validatorBalance`).
 #[receive(
     contract = "staking_bank",
     name = "balances",
@@ -154,24 +461,29 @@ fn one<S: HasStateApi>(
 )]
 fn balances<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<StakingBalanceAmount> {
+    validator_balance(ctx, host)
+}
+
+/// View function that returns the balance of an validator. This is to follow ERC20 interface (it was exposed as
+/// `balanceOf` before the staking bank became a CIS-2 token).
+#[receive(
+    contract = "staking_bank",
+    name = "validatorBalance",
+    parameter = "PublicKeyEd25519",
+    return_value = "StakingBalanceAmount"
+)]
+fn validator_balance<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<StakingBalanceAmount> {
     let key: PublicKeyEd25519 = ctx.parameter_cursor().get()?;
 
-    if is_validator(key) {
-        Ok(ONE)
-    } else {
-        Ok(0u8)
-    }
+    Ok(host.state().balance_of(&key))
 }
 
-/// View function that returns a true, if all of the provided public keys are validators, otherwise a false.
-#[cfg(any(
-    feature = "production",
-    feature = "development",
-    feature = "sandbox",
-    feature = "local"
-))]
+/// View function that returns a true, if all of the provided public keys are validators that are not jailed, otherwise a false.
 #[receive(
     contract = "staking_bank",
     name = "verifyValidators",
@@ -180,12 +492,12 @@ fn balances<S: HasStateApi>(
 )]
 fn verify_validators<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<bool> {
     let keys: Vec<PublicKeyEd25519> = ctx.parameter_cursor().get()?;
 
     for validator in keys {
-        if !is_validator(validator) {
+        if !host.state().is_active_validator(&validator) {
             return Ok(false);
         }
     }
@@ -193,13 +505,48 @@ fn verify_validators<S: HasStateApi>(
     Ok(true)
 }
 
+/// The return_value type for the contract function `verifyValidatorsDetailed`.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct VerifyValidatorsResult {
+    /// The index (in the provided list) and the public key of every provided key that is not a validator or is jailed.
+    pub invalid_validators: Vec<(u32, PublicKeyEd25519)>,
+    /// The total balance (including delegated stake) of the provided keys that are validators and not jailed.
+    pub valid_balance: StakingBalanceAmount,
+}
+
+/// View function that returns the provided public keys that are not validators or are jailed (together with their index
+/// in the provided list) and the total balance of the provided public keys that are validators and not jailed.
+#[receive(
+    contract = "staking_bank",
+    name = "verifyValidatorsDetailed",
+    parameter = "Vec<PublicKeyEd25519>",
+    return_value = "VerifyValidatorsResult"
+)]
+fn verify_validators_detailed<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<VerifyValidatorsResult> {
+    let keys: Vec<PublicKeyEd25519> = ctx.parameter_cursor().get()?;
+
+    let mut result = VerifyValidatorsResult {
+        invalid_validators: Vec::new(),
+        valid_balance: 0,
+    };
+
+    for (index, validator) in keys.into_iter().enumerate() {
+        if host.state().is_active_validator(&validator) {
+            result.valid_balance = result
+                .valid_balance
+                .saturating_add(host.state().balance_of(&validator));
+        } else {
+            result.invalid_validators.push((index as u32, validator));
+        }
+    }
+
+    Ok(result)
+}
+
 /// View function that returns the number of validtors.
-#[cfg(any(
-    feature = "production",
-    feature = "development",
-    feature = "sandbox",
-    feature = "local"
-))]
 #[receive(
     contract = "staking_bank",
     name = "getNumberOfValidators",
@@ -207,18 +554,12 @@ fn verify_validators<S: HasStateApi>(
 )]
 fn get_number_of_validators<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<u8> {
-    Ok(NUMBER_OF_VALIDATORS)
+    Ok(host.state().number_of_validators())
 }
 
 /// View function that returns the balances of validators.
-#[cfg(any(
-    feature = "production",
-    feature = "development",
-    feature = "sandbox",
-    feature = "local"
-))]
 #[receive(
     contract = "staking_bank",
     name = "getBalances",
@@ -226,23 +567,20 @@ fn get_number_of_validators<S: HasStateApi>(
 )]
 fn get_balances<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<Vec<StakingBalanceAmount>> {
-    let mut balances = Vec::with_capacity(NUMBER_OF_VALIDATORS as usize);
-    for _i in 0..NUMBER_OF_VALIDATORS {
-        balances.push(ONE)
-    }
+    let state = host.state();
+
+    let balances = state
+        .public_keys
+        .iter()
+        .map(|key| state.balance_of(key))
+        .collect();
 
     Ok(balances)
 }
 
 /// View function that returns the public key of a validator from an index.
-#[cfg(any(
-    feature = "production",
-    feature = "development",
-    feature = "sandbox",
-    feature = "local"
-))]
 #[receive(
     contract = "staking_bank",
     name = "publicKey",
@@ -251,67 +589,934 @@ fn get_balances<S: HasStateApi>(
 )]
 fn public_key<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<PublicKeyEd25519> {
     let index: u8 = ctx.parameter_cursor().get()?;
-    Ok(public_keys()[usize::from(index)])
+
+    let key = host
+        .state()
+        .public_keys
+        .get(usize::from(index))
+        .ok_or(CustomContractError::IndexOutOfBounds)?;
+
+    Ok(*key)
 }
 
-/// View function that returns the balance of an validator. This is to follow ERC20 interface.
-#[cfg(any(
-    feature = "production",
-    feature = "development",
-    feature = "sandbox",
-    feature = "local"
-))]
+/// View function that returns the total supply value. This is to follow ERC20 interface.
 #[receive(
     contract = "staking_bank",
-    name = "balanceOf",
-    parameter = "PublicKeyEd25519",
+    name = "totalSupply",
     return_value = "StakingBalanceAmount"
 )]
-fn balance_of<S: HasStateApi>(
-    ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+fn total_supply_2<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<StakingBalanceAmount> {
+    // total supply = number_of_validators * ONE + total delegated stake.
+    Ok(host.state().total_supply())
+}
+
+/// View function that returns validator's URL (as well as the inputted public key). The function throws an error if the public key is not a validator.
+#[receive(
+    contract = "staking_bank",
+    name = "validators",
+    parameter = "PublicKeyEd25519",
+    return_value = "(PublicKeyEd25519,String)"
+)]
+fn validators<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<(PublicKeyEd25519, String)> {
+    let id: PublicKeyEd25519 = ctx.parameter_cursor().get()?;
+
+    let info = host
+        .state()
+        .validators
+        .get(&id)
+        .ok_or(CustomContractError::NotValidator)?;
+
+    Ok((id, info.url.clone()))
+}
+
+/// View function that returns all validators' public keys.
+#[receive(
+    contract = "staking_bank",
+    name = "getPublicKeys",
+    return_value = "Vec<PublicKeyEd25519>"
+)]
+fn get_public_keys<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<PublicKeyEd25519>> {
+    Ok(host.state().public_keys.clone())
+}
+
+/// The parameter type for the contract function `getValidators`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct GetValidatorsParams {
+    /// The index of the first validator to return.
+    pub offset: u8,
+    /// The maximum number of validators to return.
+    pub limit: u8,
+}
+
+/// Part of the return_value type for the contract function `getValidators`.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ValidatorRecord {
+    /// The public key of the validator.
+    pub public_key: PublicKeyEd25519,
+    /// The URL of the validator.
+    pub url: String,
+    /// The balance of the validator.
+    pub balance: StakingBalanceAmount,
+}
+
+/// View function that returns up to `limit` validators (public key, URL and balance) starting at the index `offset`.
+/// The function throws an error if the `offset` is greater than the number of validators.
+#[receive(
+    contract = "staking_bank",
+    name = "getValidators",
+    parameter = "GetValidatorsParams",
+    return_value = "Vec<ValidatorRecord>"
+)]
+fn get_validators<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<ValidatorRecord>> {
+    let params: GetValidatorsParams = ctx.parameter_cursor().get()?;
+
+    let state = host.state();
+
+    let public_keys = state
+        .public_keys
+        .get(usize::from(params.offset)..)
+        .ok_or(CustomContractError::IndexOutOfBounds)?;
+
+    let mut validators = Vec::with_capacity(public_keys.len().min(usize::from(params.limit)));
+
+    for key in public_keys.iter().take(usize::from(params.limit)) {
+        let info = state
+            .validators
+            .get(key)
+            .ok_or(CustomContractError::NotValidator)?;
+
+        validators.push(ValidatorRecord {
+            public_key: *key,
+            url: info.url.clone(),
+            balance: info.balance(),
+        });
+    }
+
+    Ok(validators)
+}
+
+/// Function to record the participation of validators in a successful price update. Only the `UmbrellaFeeds` contract
+/// registered in the registry can invoke this entry point. Public keys that are not validators are ignored.
+#[receive(
+    contract = "staking_bank",
+    name = "reportParticipation",
+    parameter = "Vec<PublicKeyEd25519>",
+    error = "CustomContractError",
+    mutable
+)]
+fn report_participation<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> Result<(), CustomContractError> {
+    // Check that the sender is a contract before looking up the `UmbrellaFeeds` contract in the registry.
+    ensure!(
+        matches!(ctx.sender(), Address::Contract(_)),
+        CustomContractError::Unauthorized
+    );
+
+    let umbrella_feeds = host.invoke_contract_read_only(
+        &host.state().registry,
+        &String::from("UmbrellaFeeds"),
+        EntrypointName::new_unchecked("getAddress"),
+        Amount::zero(),
+    )?;
+
+    let umbrella_feeds: ContractAddress = umbrella_feeds
+        .ok_or(CustomContractError::InvokeContractError)?
+        .get()?;
+
+    ensure_eq!(
+        ctx.sender(),
+        Address::Contract(umbrella_feeds),
+        CustomContractError::Unauthorized
+    );
+
+    let keys: Vec<PublicKeyEd25519> = ctx.parameter_cursor().get()?;
+
+    let now = ctx.metadata().slot_time();
+
+    let state = host.state_mut();
+
+    state.last_report = now;
+
+    for key in keys {
+        if let Some(mut info) = state.validators.get_mut(&key) {
+            info.last_participation = now;
+        }
+    }
+
+    Ok(())
+}
+
+/// Function to unjail a validator. Only the owner can invoke this entry point.
+#[receive(
+    contract = "staking_bank",
+    name = "unjail",
+    parameter = "PublicKeyEd25519",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn unjail<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure_eq!(
+        ctx.sender(),
+        host.state().owner,
+        CustomContractError::Unauthorized
+    );
+
     let key: PublicKeyEd25519 = ctx.parameter_cursor().get()?;
 
-    if is_validator(key) {
-        Ok(ONE)
+    unjail_validator(ctx, host, logger, key)
+}
+
+/// The message that a validator signs to unjail itself via the `selfUnjail` entry point.
+#[derive(Debug, Serialize, SchemaType, Clone)]
+pub struct UnjailMessage {
+    /// The contract_address that the signature is intended for.
+    pub contract_address: ContractAddress,
+    /// A timestamp to make signatures expire.
+    pub timestamp: Timestamp,
+    /// The current last participation of the validator (see `validatorStatus`). Unjailing resets the last
+    /// participation, so a signature can only be used once.
+    pub last_participation: Timestamp,
+}
+
+/// The parameter type for the contract function `selfUnjail`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct SelfUnjailParams {
+    /// The public key of the validator.
+    pub public_key: PublicKeyEd25519,
+    /// The signature of the validator on the hash of the `message`.
+    pub signature: SignatureEd25519,
+    /// Message that was signed.
+    pub message: UnjailMessage,
+}
+
+/// Helper function to calculate the hash of the `UnjailMessage` that a validator signs.
+#[receive(
+    contract = "staking_bank",
+    name = "viewUnjailMessageHash",
+    parameter = "UnjailMessage",
+    return_value = "HashSha2256",
+    crypto_primitives
+)]
+fn view_unjail_message_hash<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ReceiveResult<HashSha2256> {
+    let message: UnjailMessage = ctx.parameter_cursor().get()?;
+
+    Ok(crypto_primitives.hash_sha2_256(&to_bytes(&message)))
+}
+
+/// Function to unjail a validator with a signature of its validator key. Anyone can invoke this entry point.
+#[receive(
+    contract = "staking_bank",
+    name = "selfUnjail",
+    parameter = "SelfUnjailParams",
+    error = "CustomContractError",
+    crypto_primitives,
+    enable_logger,
+    mutable
+)]
+fn self_unjail<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> Result<(), CustomContractError> {
+    let param: SelfUnjailParams = ctx.parameter_cursor().get()?;
+
+    // Check that the signature was intended for this contract.
+    ensure_eq!(
+        param.message.contract_address,
+        ctx.self_address(),
+        CustomContractError::WrongContract
+    );
+
+    // Check signature is not expired.
+    ensure!(
+        param.message.timestamp > ctx.metadata().slot_time(),
+        CustomContractError::Expired
+    );
+
+    let message_hash = crypto_primitives.hash_sha2_256(&to_bytes(&param.message));
+
+    ensure!(
+        crypto_primitives.verify_ed25519_signature(
+            param.public_key,
+            param.signature,
+            &message_hash.0
+        ),
+        CustomContractError::WrongSignature
+    );
+
+    // Check that the message was not used before.
+    let last_participation = host
+        .state()
+        .validators
+        .get(&param.public_key)
+        .ok_or(CustomContractError::NotValidator)?
+        .last_participation;
+
+    ensure_eq!(
+        param.message.last_participation,
+        last_participation,
+        CustomContractError::OutdatedUnjailMessage
+    );
+
+    unjail_validator(ctx, host, logger, param.public_key)
+}
+
+/// Internal function that unjails a validator by resetting its last participation to the current time. It rejects if the
+/// validator is not jailed.
+fn unjail_validator<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    key: PublicKeyEd25519,
+) -> Result<(), CustomContractError> {
+    let now = ctx.metadata().slot_time();
+
+    let state = host.state_mut();
+
+    let jailed = state
+        .validators
+        .get(&key)
+        .map(|info| state.is_inactive(&info))
+        .ok_or(CustomContractError::NotValidator)?;
+
+    ensure!(jailed, CustomContractError::NotJailed);
+
+    if let Some(mut info) = state.validators.get_mut(&key) {
+        info.last_participation = now;
+    }
+
+    // Log Unjailed event
+    logger.log(&Event::Unjailed(UnjailedEvent {
+        public_key: key,
+        unjailed_by: ctx.sender(),
+    }))?;
+
+    Ok(())
+}
+
+/// Function to set the liveness window. Only the owner can invoke this entry point.
+#[receive(
+    contract = "staking_bank",
+    name = "setLivenessWindow",
+    parameter = "Duration",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn set_liveness_window<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure_eq!(
+        ctx.sender(),
+        host.state().owner,
+        CustomContractError::Unauthorized
+    );
+
+    let new_liveness_window: Duration = ctx.parameter_cursor().get()?;
+
+    ensure!(
+        new_liveness_window.millis() > 0,
+        CustomContractError::InvalidLivenessWindow
+    );
+
+    let previous_liveness_window = host.state().liveness_window;
+
+    host.state_mut().liveness_window = new_liveness_window;
+
+    // Log LivenessWindowUpdated event
+    logger.log(&Event::LivenessWindowUpdated(LivenessWindowUpdatedEvent {
+        previous_liveness_window,
+        new_liveness_window,
+    }))?;
+
+    Ok(())
+}
+
+/// View function that returns the liveness window.
+#[receive(
+    contract = "staking_bank",
+    name = "livenessWindow",
+    return_value = "Duration"
+)]
+fn liveness_window<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Duration> {
+    Ok(host.state().liveness_window)
+}
+
+/// The return_value type for the contract function `validatorStatus`.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ValidatorStatus {
+    /// The last time the validator participated in a successful price update (or was unjailed).
+    pub last_participation: Timestamp,
+    /// True if the last report was received more than the liveness window after the last participation.
+    pub jailed: bool,
+}
+
+/// View function that returns the last participation and the jail status of a validator. The function throws an error if
+/// the public key is not a validator.
+#[receive(
+    contract = "staking_bank",
+    name = "validatorStatus",
+    parameter = "PublicKeyEd25519",
+    return_value = "ValidatorStatus"
+)]
+fn validator_status<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<ValidatorStatus> {
+    let key: PublicKeyEd25519 = ctx.parameter_cursor().get()?;
+
+    let state = host.state();
+
+    let info = state
+        .validators
+        .get(&key)
+        .ok_or(CustomContractError::NotValidator)?;
+
+    Ok(ValidatorStatus {
+        last_participation: info.last_participation,
+        jailed: state.is_inactive(&info),
+    })
+}
+
+/// View function that returns the owner address.
+#[receive(contract = "staking_bank", name = "owner", return_value = "Address")]
+fn owner<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Address> {
+    Ok(host.state().owner)
+}
+
+/// Function to delegate the sent CCD to a validator. Only accounts can delegate.
+#[receive(
+    contract = "staking_bank",
+    name = "delegate",
+    parameter = "PublicKeyEd25519",
+    error = "CustomContractError",
+    enable_logger,
+    payable,
+    mutable
+)]
+fn delegate<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    amount: Amount,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    let delegator = match ctx.sender() {
+        Address::Account(account) => account,
+        Address::Contract(_) => return Err(CustomContractError::OnlyAccount),
+    };
+
+    ensure!(amount > Amount::zero(), CustomContractError::InvalidAmount);
+
+    let validator: PublicKeyEd25519 = ctx.parameter_cursor().get()?;
+
+    let state = host.state_mut();
+
+    {
+        let mut info = state
+            .validators
+            .get_mut(&validator)
+            .ok_or(CustomContractError::NotValidator)?;
+
+        info.delegated_stake = info
+            .delegated_stake
+            .micro_ccd
+            .checked_add(amount.micro_ccd)
+            .map(Amount::from_micro_ccd)
+            .ok_or(CustomContractError::Overflow)?;
+    }
+
+    state.total_delegated_stake = state
+        .total_delegated_stake
+        .micro_ccd
+        .checked_add(amount.micro_ccd)
+        .map(Amount::from_micro_ccd)
+        .ok_or(CustomContractError::Overflow)?;
+
+    let mut delegation = state
+        .delegations
+        .entry((delegator, validator))
+        .or_insert(Amount::zero());
+    *delegation += amount;
+    let delegation = *delegation;
+
+    // Log Delegated event
+    logger.log(&Event::Delegated(DelegatedEvent {
+        delegator,
+        validator,
+        amount,
+        delegation,
+    }))?;
+
+    // Log CIS-2 Mint event
+    logger.log(&Event::Cis2Event(Cis2Event::Mint(MintEvent {
+        token_id: TokenIdFixed(validator.0),
+        amount: TokenAmountU64(amount.micro_ccd),
+        owner: Address::Account(delegator),
+    })))?;
+
+    Ok(())
+}
+
+/// The parameter type for the contract function `undelegate`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct UndelegateParams {
+    /// The public key of the validator.
+    pub validator: PublicKeyEd25519,
+    /// The amount of CCD to undelegate.
+    pub amount: Amount,
+}
+
+/// Function to undelegate CCD from a validator. The undelegated CCD is added to the unbonding CCD of the delegator with
+/// the current time plus the unbonding period as release time. CCD that is already unbonding keeps its release time.
+#[receive(
+    contract = "staking_bank",
+    name = "undelegate",
+    parameter = "UndelegateParams",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn undelegate<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    let delegator = match ctx.sender() {
+        Address::Account(account) => account,
+        Address::Contract(_) => return Err(CustomContractError::OnlyAccount),
+    };
+
+    let param: UndelegateParams = ctx.parameter_cursor().get()?;
+
+    ensure!(
+        param.amount > Amount::zero(),
+        CustomContractError::InvalidAmount
+    );
+
+    let release_time = ctx
+        .metadata()
+        .slot_time()
+        .checked_add(host.state().unbonding_period)
+        .ok_or(CustomContractError::Overflow)?;
+
+    let state = host.state_mut();
+
+    let delegation = {
+        let mut delegation = state
+            .delegations
+            .get_mut(&(delegator, param.validator))
+            .ok_or(CustomContractError::InsufficientDelegation)?;
+
+        ensure!(
+            *delegation >= param.amount,
+            CustomContractError::InsufficientDelegation
+        );
+
+        *delegation -= param.amount;
+        *delegation
+    };
+
+    if delegation == Amount::zero() {
+        state.delegations.remove(&(delegator, param.validator));
+    }
+
+    // The delegated stake of the validator always includes the delegation of the delegator.
+    if let Some(mut info) = state.validators.get_mut(&param.validator) {
+        info.delegated_stake -= param.amount;
+    }
+    state.total_delegated_stake -= param.amount;
+
+    let mut unbondings = state.unbondings.entry(delegator).or_insert(Vec::new());
+
+    // The unbonding period is fixed, so the new entry is always the latest. Undelegations with the same release time
+    // (e.g. in the same block) are merged.
+    match unbondings.last_mut() {
+        Some(unbonding) if unbonding.release_time == release_time => {
+            unbonding.amount = unbonding
+                .amount
+                .micro_ccd
+                .checked_add(param.amount.micro_ccd)
+                .map(Amount::from_micro_ccd)
+                .ok_or(CustomContractError::Overflow)?;
+        }
+        _ => unbondings.push(Unbonding {
+            amount: param.amount,
+            release_time,
+        }),
+    }
+
+    // Log Undelegated event
+    logger.log(&Event::Undelegated(UndelegatedEvent {
+        delegator,
+        validator: param.validator,
+        amount: param.amount,
+        delegation,
+        release_time,
+    }))?;
+
+    // Log CIS-2 Burn event
+    logger.log(&Event::Cis2Event(Cis2Event::Burn(BurnEvent {
+        token_id: TokenIdFixed(param.validator.0),
+        amount: TokenAmountU64(param.amount.micro_ccd),
+        owner: Address::Account(delegator),
+    })))?;
+
+    Ok(())
+}
+
+/// Function to withdraw all unbonding CCD of the invoker whose unbonding period has passed. CCD that is still unbonding
+/// stays locked until its release time. The tokens were already burned by `undelegate`, so no CIS-2 event is logged.
+#[receive(
+    contract = "staking_bank",
+    name = "withdraw",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn withdraw<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    let delegator = match ctx.sender() {
+        Address::Account(account) => account,
+        Address::Contract(_) => return Err(CustomContractError::OnlyAccount),
+    };
+
+    let now = ctx.metadata().slot_time();
+
+    let mut unbondings = host
+        .state()
+        .unbondings
+        .get(&delegator)
+        .map(|unbondings| unbondings.clone())
+        .ok_or(CustomContractError::NothingToWithdraw)?;
+
+    // The entries are ordered by release time, so the released entries are at the front.
+    let released = unbondings
+        .iter()
+        .take_while(|unbonding| unbonding.release_time <= now)
+        .count();
+
+    ensure!(released > 0, CustomContractError::NothingToWithdraw);
+
+    let mut amount = Amount::zero();
+    for unbonding in unbondings.drain(..released) {
+        amount = amount
+            .micro_ccd
+            .checked_add(unbonding.amount.micro_ccd)
+            .map(Amount::from_micro_ccd)
+            .ok_or(CustomContractError::Overflow)?;
+    }
+
+    if unbondings.is_empty() {
+        host.state_mut().unbondings.remove(&delegator);
     } else {
-        Ok(0u8)
+        let _ = host.state_mut().unbondings.insert(delegator, unbondings);
     }
+
+    // Log Withdrawn event
+    logger.log(&Event::Withdrawn(WithdrawnEvent { delegator, amount }))?;
+
+    host.invoke_transfer(&delegator, amount)
+        .map_err(|_| CustomContractError::InvokeTransferError)?;
+
+    Ok(())
 }
 
-/// View function that returns the total supply value. This is to follow ERC20 interface.
-#[cfg(any(
-    feature = "production",
-    feature = "development",
-    feature = "sandbox",
-    feature = "local"
-))]
+/// The parameter type for the contract function `delegation`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct DelegationQuery {
+    /// The delegator.
+    pub delegator: AccountAddress,
+    /// The public key of the validator.
+    pub validator: PublicKeyEd25519,
+}
+
+/// View function that returns the amount of CCD a delegator has delegated to a validator.
 #[receive(
     contract = "staking_bank",
-    name = "totalSupply",
-    return_value = "StakingBalanceAmount"
+    name = "delegation",
+    parameter = "DelegationQuery",
+    return_value = "Amount"
 )]
-fn total_supply_2<S: HasStateApi>(
+fn delegation<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Amount> {
+    let query: DelegationQuery = ctx.parameter_cursor().get()?;
+
+    Ok(host
+        .state()
+        .delegations
+        .get(&(query.delegator, query.validator))
+        .map_or(Amount::zero(), |delegation| *delegation))
+}
+
+/// View function that returns the total amount of CCD delegated to a validator. The function throws an error if the
+/// public key is not a validator.
+#[receive(
+    contract = "staking_bank",
+    name = "delegatedStake",
+    parameter = "PublicKeyEd25519",
+    return_value = "Amount"
+)]
+fn delegated_stake<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Amount> {
+    let key: PublicKeyEd25519 = ctx.parameter_cursor().get()?;
+
+    let info = host
+        .state()
+        .validators
+        .get(&key)
+        .ok_or(CustomContractError::NotValidator)?;
+
+    Ok(info.delegated_stake)
+}
+
+/// View function that returns the unbonding CCD of a delegator (ordered by release time, oldest first).
+#[receive(
+    contract = "staking_bank",
+    name = "unbonding",
+    parameter = "AccountAddress",
+    return_value = "Vec<Unbonding>"
+)]
+fn unbonding<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<Unbonding>> {
+    let delegator: AccountAddress = ctx.parameter_cursor().get()?;
+
+    Ok(host
+        .state()
+        .unbondings
+        .get(&delegator)
+        .map_or(Vec::new(), |unbondings| unbondings.clone()))
+}
+
+/// View function that returns the unbonding period.
+#[receive(
+    contract = "staking_bank",
+    name = "unbondingPeriod",
+    return_value = "Duration"
+)]
+fn unbonding_period<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
-) -> ReceiveResult<StakingBalanceAmount> {
-    Ok(TOTAL_SUPPLY)
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Duration> {
+    Ok(host.state().unbonding_period)
+}
+
+/// Function to query the CIS-2 token balances of a list of addresses. The balance of an account is the CCD (in micro
+/// CCD) it delegated to the validator of the token; contracts cannot delegate and always have a zero balance.
+/// The function throws an error if a token ID is not the public key of a validator.
+#[receive(
+    contract = "staking_bank",
+    name = "balanceOf",
+    parameter = "ContractBalanceOfQueryParams",
+    return_value = "ContractBalanceOfQueryResponse",
+    error = "ContractError"
+)]
+fn contract_balance_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ContractBalanceOfQueryResponse> {
+    let params: ContractBalanceOfQueryParams = ctx.parameter_cursor().get()?;
+
+    let state = host.state();
+
+    let mut response = Vec::with_capacity(params.queries.len());
+
+    for query in params.queries {
+        let validator = PublicKeyEd25519(query.token_id.0);
+
+        ensure!(
+            state.validators.get(&validator).is_some(),
+            ContractError::InvalidTokenId
+        );
+
+        let amount = match query.address {
+            Address::Account(delegator) => state
+                .delegations
+                .get(&(delegator, validator))
+                .map_or(0, |delegation| delegation.micro_ccd),
+            Address::Contract(_) => 0,
+        };
+
+        response.push(TokenAmountU64(amount));
+    }
+
+    Ok(BalanceOfQueryResponse::from(response))
+}
+
+/// Function to query the CIS-2 token metadata URLs. The metadata URL of a token is the URL of its validator.
+/// The function throws an error if a token ID is not the public key of a validator.
+#[receive(
+    contract = "staking_bank",
+    name = "tokenMetadata",
+    parameter = "ContractTokenMetadataQueryParams",
+    return_value = "TokenMetadataQueryResponse",
+    error = "ContractError"
+)]
+fn contract_token_metadata<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<TokenMetadataQueryResponse> {
+    let params: ContractTokenMetadataQueryParams = ctx.parameter_cursor().get()?;
+
+    let mut response = Vec::with_capacity(params.queries.len());
+
+    for token_id in params.queries {
+        let info = host
+            .state()
+            .validators
+            .get(&PublicKeyEd25519(token_id.0))
+            .ok_or(ContractError::InvalidTokenId)?;
+
+        response.push(MetadataUrl {
+            url: info.url.clone(),
+            hash: None,
+        });
+    }
+
+    Ok(TokenMetadataQueryResponse::from(response))
+}
+
+/// CIS-2 transfer function. The token is non-transferable, so it always rejects with `Unauthorized`. Delegated CCD only
+/// moves via `delegate`/`undelegate`.
+#[receive(
+    contract = "staking_bank",
+    name = "transfer",
+    parameter = "ContractTransferParams",
+    error = "ContractError"
+)]
+fn contract_transfer<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let _params: ContractTransferParams = ctx.parameter_cursor().get()?;
+
+    Err(ContractError::Unauthorized)
+}
+
+/// CIS-2 function to add or remove operators. The token is non-transferable, so operators are not supported and it always
+/// rejects with `Unauthorized`.
+#[receive(
+    contract = "staking_bank",
+    name = "updateOperator",
+    parameter = "UpdateOperatorParams",
+    error = "ContractError"
+)]
+fn contract_update_operator<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let _params: UpdateOperatorParams = ctx.parameter_cursor().get()?;
+
+    Err(ContractError::Unauthorized)
+}
+
+/// CIS-2 function to query whether an address is an operator of an owner. Operators are not supported, so it returns false
+/// for every query.
+#[receive(
+    contract = "staking_bank",
+    name = "operatorOf",
+    parameter = "OperatorOfQueryParams",
+    return_value = "OperatorOfQueryResponse",
+    error = "ContractError"
+)]
+fn contract_operator_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<OperatorOfQueryResponse> {
+    let params: OperatorOfQueryParams = ctx.parameter_cursor().get()?;
+
+    Ok(OperatorOfQueryResponse::from(vec![
+        false;
+        params.queries.len()
+    ]))
+}
+
+/// Function to query whether the contract supports a list of standards (CIS-0). The contract supports CIS-0 and CIS-2.
+#[receive(
+    contract = "staking_bank",
+    name = "supports",
+    parameter = "SupportsQueryParams",
+    return_value = "SupportsQueryResponse",
+    error = "ContractError"
+)]
+fn contract_supports<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<SupportsQueryResponse> {
+    let params: SupportsQueryParams = ctx.parameter_cursor().get()?;
+
+    let response = params
+        .queries
+        .iter()
+        .map(|std_id| {
+            if SUPPORTS_STANDARDS.contains(&std_id.as_standard_identifier()) {
+                SupportResult::Support
+            } else {
+                SupportResult::NoSupport
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(SupportsQueryResponse::from(response))
 }
 
 /// View function that returns the key/name of this contract.
 #[receive(contract = "staking_bank", name = "getName", return_value = "String")]
 fn get_name<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<&'static str> {
     Ok("StakingBank")
 }
 
-/// The parameter type for the contract function `upgrade`.
+/// The parameter type for the contract function `upgradeNatively`.
+/// Takes the new module and optionally an entrypoint to call in the new module
+/// after triggering the upgrade. The upgrade is reverted if the entrypoint invoke
+/// fails. This is useful for doing migration in the same transaction triggering
+/// the upgrade.
 #[derive(Debug, Serialize, SchemaType)]
 pub struct UpgradeParams {
     /// The new module reference.
@@ -320,7 +1525,22 @@ pub struct UpgradeParams {
     pub migrate: Option<(OwnedEntrypointName, OwnedParameter)>,
 }
 
-/// Hook function to enable `atomicUpdate` via the registry contract.
+/// This function is a hook function intended to be invoked via the `atomicUpdate` function in the registry contract.
+/// This function upgrades this smart contract instance to a new module and calls optionally a
+/// migration function after the upgrade.
+///
+/// It rejects if:
+/// - Sender is not the registry contract instance.
+/// - It fails to parse the parameter.
+/// - If the upgrade fails.
+/// - If the migration invoke fails.
+///
+/// This function is marked as `low_level`. This is **necessary** since the
+/// high-level mutable functions store the state of the contract at the end of
+/// execution. This conflicts with migration since the shape of the state
+/// **might** be changed by the migration function. If the state is then written
+/// by this function it would overwrite the state stored by the migration
+/// function.
 #[receive(
     contract = "staking_bank",
     name = "upgradeNatively",
@@ -329,21 +1549,56 @@ pub struct UpgradeParams {
     low_level
 )]
 fn upgrade_natively<S: HasStateApi>(
-    _ctx: &impl HasReceiveContext,
-    _host: &mut impl HasHost<S>,
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<S>,
 ) -> Result<(), CustomContractError> {
-    // There are no requirements atm
+    // Read the top-level contract state.
+    let state: State<S> = host.state().read_root()?;
+
+    // Only the registry can upgrade this contract.
+    ensure!(
+        ctx.sender().matches_contract(&state.registry),
+        CustomContractError::Unauthorized
+    );
+
+    // Parse the parameter.
+    let param: UpgradeParams = ctx.parameter_cursor().get()?;
+
+    // Trigger the upgrade.
+    host.upgrade(param.module)?;
+
+    // Call the migration function if provided.
+    if let Some((func, parameters)) = param.migrate {
+        host.invoke_contract_raw(
+            &ctx.self_address(),
+            parameters.as_parameter(),
+            func.as_entrypoint_name(),
+            Amount::zero(),
+        )?;
+    }
 
     Ok(())
 }
 
-/// Hook function to enable `atomicUpdate` via the registry contract.
-#[receive(contract = "staking_bank", name = "unregister")]
+/// Hook function to enable `atomicUpdate` via the registry contract. It is invoked by the registry when this contract
+/// is replaced by another contract (or removed from the registry).
+///
+/// It rejects if:
+/// - Sender is not the registry contract instance.
+#[receive(
+    contract = "staking_bank",
+    name = "unregister",
+    error = "CustomContractError"
+)]
 fn unregister<S: HasStateApi>(
-    _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
-) -> ReceiveResult<()> {
-    // There are no requirements atm
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> Result<(), CustomContractError> {
+    // Only the registry can unregister this contract.
+    ensure!(
+        ctx.sender().matches_contract(&host.state().registry),
+        CustomContractError::Unauthorized
+    );
 
     Ok(())
 }
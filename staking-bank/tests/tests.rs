@@ -1,7 +1,43 @@
+use concordium_cis2::{
+    AdditionalData, BalanceOfQuery, BurnEvent, Cis2Event, MintEvent, OperatorOfQuery,
+    OperatorOfQueryParams, OperatorOfQueryResponse, OperatorUpdate, Receiver, SupportResult,
+    SupportsQueryParams, SupportsQueryResponse, TokenAmountU64, TokenIdFixed, TokenMetadataEvent,
+    TokenMetadataQueryParams, TokenMetadataQueryResponse, Transfer, UpdateOperator,
+    UpdateOperatorParams, CIS0_STANDARD_IDENTIFIER, CIS1_STANDARD_IDENTIFIER,
+    CIS2_STANDARD_IDENTIFIER,
+};
 use concordium_smart_contract_testing::*;
-use concordium_std::{HashSha2256, PublicKeyEd25519};
+use concordium_std::{HashSha2256, MetadataUrl, PublicKeyEd25519};
+use concordium_std::{SignatureEd25519, Timestamp};
+use staking_bank::{
+    ContractBalanceOfQueryParams, ContractBalanceOfQueryResponse, ContractTokenAmount,
+    ContractTokenId, ContractTransferParams, DelegatedEvent, DelegationQuery, GetValidatorsParams,
+    InitParamsStakingBank, SelfUnjailParams, Unbonding, UndelegateParams, UndelegatedEvent,
+    UnjailMessage, UpgradeParams, ValidatorRecord, ValidatorStatus, VerifyValidatorsResult,
+    WithdrawnEvent,
+};
 
 const ACC_ADDR_OWNER: AccountAddress = AccountAddress([77u8; 32]);
+const ACC_ADDR_OTHER: AccountAddress = AccountAddress([1u8; 32]);
+
+// The registry is only used to look up the `UmbrellaFeeds` contract in `reportParticipation`.
+const REGISTRY: ContractAddress = ContractAddress {
+    index: 1000,
+    subindex: 0,
+};
+
+const LIVENESS_WINDOW: Duration = Duration::from_seconds(3600);
+
+const UNBONDING_PERIOD: Duration = Duration::from_seconds(86400);
+
+// Signature of VALIDATOR_1 on the hash of the `UnjailMessage` for the staking bank contract <0,0> with the timestamp 10000000000
+// and the last participation 0.
+const SIGNATURE_UNJAIL_VALIDATOR_1: SignatureEd25519 = SignatureEd25519([
+    116, 249, 246, 12, 218, 79, 6, 142, 154, 20, 95, 74, 165, 193, 217, 10, 52, 236, 128, 234, 241,
+    164, 215, 30, 19, 205, 113, 33, 202, 164, 133, 129, 80, 227, 186, 74, 162, 217, 202, 181, 34,
+    159, 183, 114, 31, 29, 135, 48, 175, 224, 7, 34, 184, 253, 225, 54, 172, 72, 44, 39, 115, 254,
+    119, 8,
+]);
 
 // ATTENTION: Use a different key in production. This key and its private key is exposed and used for testing here.
 // Private key: 8ECA45107A878FB879B84401084B55AD4919FC0F7D14E8915D8A5989B1AE1C01
@@ -24,11 +60,88 @@ const VALIDATOR_DOES_NOT_EXIST: PublicKeyEd25519 = PublicKeyEd25519([
 
 const ACC_INITIAL_BALANCE: Amount = Amount::from_ccd(1000);
 
+// The balance of a validator without any delegated CCD (1 CCD in micro CCD).
+const ONE: u64 = 1_000_000;
+
 const KEY_HASH_1: HashSha2256 = HashSha2256([
     120, 154, 141, 6, 248, 239, 77, 224, 80, 62, 139, 136, 211, 204, 105, 208, 26, 11, 2, 208, 195,
     253, 29, 192, 126, 199, 208, 39, 69, 4, 246, 32,
 ]);
 
+/// Returns the reject reason of a failed invoke if the contract rejected on its own.
+fn reject_reason(error: &ContractInvokeError) -> Option<i32> {
+    match &error.kind {
+        ContractInvokeErrorKind::ExecutionError {
+            failure_kind: InvokeFailure::ContractReject { code, .. },
+        } => Some(*code),
+        _ => None,
+    }
+}
+
+/// Invokes `verifyValidators` with the given public keys.
+fn verify_validators(
+    chain: &Chain,
+    staking_bank: ContractAddress,
+    validators: Vec<PublicKeyEd25519>,
+) -> bool {
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.verifyValidators".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&validators)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query if address is validator");
+
+    from_bytes(&invoke.return_value).expect("Should return a valid result")
+}
+
+/// Invokes `balances` with the given public key.
+fn balance_of(chain: &Chain, staking_bank: ContractAddress, validator: PublicKeyEd25519) -> u64 {
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.balances".to_string()),
+                message: OwnedParameter::from_serial(&validator)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query the balance");
+
+    from_bytes(&invoke.return_value).expect("Should return a valid result")
+}
+
+fn init_params() -> InitParamsStakingBank {
+    InitParamsStakingBank {
+        validators: vec![
+            (
+                VALIDATOR_0,
+                String::from("https://validator.dev.umb.network"),
+            ),
+            (
+                VALIDATOR_1,
+                String::from("https://validator2.dev.umb.network"),
+            ),
+        ],
+        registry: REGISTRY,
+        liveness_window: LIVENESS_WINDOW,
+        unbonding_period: UNBONDING_PERIOD,
+    }
+}
+
 fn setup_chain_and_contract() -> (Chain, ContractInitSuccess) {
     let mut chain = Chain::new();
 
@@ -55,7 +168,8 @@ fn setup_chain_and_contract() -> (Chain, ContractInitSuccess) {
                 amount: Amount::zero(),
                 mod_ref: deployment_staking_bank.module_reference,
                 init_name: OwnedContractName::new_unchecked("init_staking_bank".to_string()),
-                param: OwnedParameter::empty(),
+                param: OwnedParameter::from_serial(&init_params())
+                    .expect("`InitParamsStakingBank` should be a valid inut parameter"),
             },
         )
         .expect("Initialization of `Staking_bank` should always succeed");
@@ -112,6 +226,75 @@ fn test_verify_validators() {
     assert_eq!(value, true);
 }
 
+#[test]
+fn test_verify_validators_detailed() {
+    let (chain, initialization_staking_bank) = setup_chain_and_contract();
+
+    // Checking verifyValidatorsDetailed with a key that is not a validator.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_staking_bank.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.verifyValidatorsDetailed".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&vec![
+                    VALIDATOR_0,
+                    VALIDATOR_DOES_NOT_EXIST,
+                    VALIDATOR_1,
+                ])
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to verify validators");
+
+    let value: VerifyValidatorsResult =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        value,
+        VerifyValidatorsResult {
+            invalid_validators: vec![(1, VALIDATOR_DOES_NOT_EXIST)],
+            valid_balance: 2 * ONE,
+        }
+    );
+
+    // Checking verifyValidatorsDetailed with validators only.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_staking_bank.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.verifyValidatorsDetailed".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&vec![VALIDATOR_0, VALIDATOR_1])
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to verify validators");
+
+    let value: VerifyValidatorsResult =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        value,
+        VerifyValidatorsResult {
+            invalid_validators: vec![],
+            valid_balance: 2 * ONE,
+        }
+    );
+}
+
 #[test]
 fn test_balances() {
     let (chain, initialization_staking_bank) = setup_chain_and_contract();
@@ -133,9 +316,9 @@ fn test_balances() {
         )
         .expect("Should be able to query the balance");
 
-    let value: u8 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: u64 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    assert_eq!(value, 1);
+    assert_eq!(value, ONE);
 
     let invoke = chain
         .contract_invoke(
@@ -152,7 +335,7 @@ fn test_balances() {
         )
         .expect("Should be able to query the balance");
 
-    let value: u8 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: u64 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
     assert_eq!(value, 0);
 
@@ -174,13 +357,11 @@ fn test_balances() {
         )
         .expect("Should be able to query the balance");
 
-    let value: Vec<u8> = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: Vec<u64> = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    let one = 1u8;
+    assert_eq!(value, vec![ONE, ONE]);
 
-    assert_eq!(value, vec![one, one]);
-
-    // Checking balanceOf.
+    // Checking validatorBalance.
 
     let invoke = chain
         .contract_invoke(
@@ -190,16 +371,18 @@ fn test_balances() {
             UpdateContractPayload {
                 amount: Amount::zero(),
                 address: initialization_staking_bank.contract_address,
-                receive_name: OwnedReceiveName::new_unchecked("staking_bank.balanceOf".to_string()),
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.validatorBalance".to_string(),
+                ),
                 message: OwnedParameter::from_serial(&VALIDATOR_0)
                     .expect("Should be a valid inut parameter"),
             },
         )
         .expect("Should be able to query the balance");
 
-    let value: u8 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: u64 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    assert_eq!(value, 1u8);
+    assert_eq!(value, ONE);
 }
 
 #[test]
@@ -257,6 +440,126 @@ fn test_validators() {
         from_bytes(&invoke.return_value).expect("Should return a valid result");
 
     assert_eq!(value, VALIDATOR_0);
+
+    // Checking that publicKey fails for an index out of bounds.
+
+    let invoke = chain.contract_invoke(
+        ACC_ADDR_OWNER,
+        Address::Account(ACC_ADDR_OWNER),
+        Energy::from(10000),
+        UpdateContractPayload {
+            amount: Amount::zero(),
+            address: initialization_staking_bank.contract_address,
+            receive_name: OwnedReceiveName::new_unchecked("staking_bank.publicKey".to_string()),
+            message: OwnedParameter::from_serial(&2u8).expect("Should be a valid inut parameter"),
+        },
+    );
+
+    let error = invoke.expect_err("Should fail for an index out of bounds");
+
+    assert_eq!(reject_reason(&error), Some(-8), "Wrong reject reason");
+}
+
+#[test]
+fn test_get_validators() {
+    let (chain, initialization_staking_bank) = setup_chain_and_contract();
+
+    // Checking getValidators.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_staking_bank.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.getValidators".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&GetValidatorsParams {
+                    offset: 0,
+                    limit: 10,
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query validators");
+
+    let value: Vec<ValidatorRecord> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        value,
+        vec![
+            ValidatorRecord {
+                public_key: VALIDATOR_0,
+                url: String::from("https://validator.dev.umb.network"),
+                balance: ONE,
+            },
+            ValidatorRecord {
+                public_key: VALIDATOR_1,
+                url: String::from("https://validator2.dev.umb.network"),
+                balance: ONE,
+            },
+        ]
+    );
+
+    // Checking getValidators with an offset and a limit.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_staking_bank.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.getValidators".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&GetValidatorsParams {
+                    offset: 1,
+                    limit: 1,
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query validators");
+
+    let value: Vec<ValidatorRecord> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        value,
+        vec![ValidatorRecord {
+            public_key: VALIDATOR_1,
+            url: String::from("https://validator2.dev.umb.network"),
+            balance: ONE,
+        }]
+    );
+
+    // Checking that getValidators fails for an offset out of bounds.
+
+    let invoke = chain.contract_invoke(
+        ACC_ADDR_OWNER,
+        Address::Account(ACC_ADDR_OWNER),
+        Energy::from(10000),
+        UpdateContractPayload {
+            amount: Amount::zero(),
+            address: initialization_staking_bank.contract_address,
+            receive_name: OwnedReceiveName::new_unchecked("staking_bank.getValidators".to_string()),
+            message: OwnedParameter::from_serial(&GetValidatorsParams {
+                offset: 3,
+                limit: 1,
+            })
+            .expect("Should be a valid inut parameter"),
+        },
+    );
+
+    let error = invoke.expect_err("Should fail for an offset out of bounds");
+
+    assert_eq!(reject_reason(&error), Some(-8), "Wrong reject reason");
 }
 
 #[test]
@@ -284,6 +587,62 @@ fn test_get_name() {
     assert_eq!(value, String::from("StakingBank"));
 }
 
+#[test]
+fn test_upgrade_natively_and_unregister_only_by_registry() {
+    let (mut chain, initialization_staking_bank) = setup_chain_and_contract();
+
+    let module = chain
+        .get_contract(initialization_staking_bank.contract_address)
+        .expect("The staking_bank contract should exist")
+        .module_reference;
+
+    // Checking that only the registry can upgrade the staking bank.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_staking_bank.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.upgradeNatively".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&UpgradeParams {
+                    module,
+                    migrate: None,
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to upgrade without being the registry");
+
+    assert_eq!(reject_reason(&update), Some(-9));
+
+    // Checking that only the registry can unregister the staking bank.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_staking_bank.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.unregister".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect_err("Should fail to unregister without being the registry");
+
+    assert_eq!(reject_reason(&update), Some(-9));
+}
+
 #[test]
 fn test_init() {
     let (chain, initialization_staking_bank) = setup_chain_and_contract();
@@ -353,9 +712,9 @@ fn test_init() {
         )
         .expect("Should be able to query value");
 
-    let value: u8 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: u64 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    assert_eq!(value, 2);
+    assert_eq!(value, 2 * ONE);
 
     // Checking `totalSupply`.
 
@@ -376,9 +735,9 @@ fn test_init() {
         )
         .expect("Should be able to query value");
 
-    let value: u8 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: u64 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    assert_eq!(value, 2);
+    assert_eq!(value, 2 * ONE);
 
     // Checking `ONE`.
 
@@ -397,9 +756,9 @@ fn test_init() {
         )
         .expect("Should be able to query value");
 
-    let value: u8 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: u64 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    assert_eq!(value, 1);
+    assert_eq!(value, ONE);
 
     // Checking `getPublicKeys`.
 
@@ -420,8 +779,1102 @@ fn test_init() {
         )
         .expect("Should be able to query value");
 
-    let value: [PublicKeyEd25519; 2] =
+    let value: Vec<PublicKeyEd25519> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(value, vec![VALIDATOR_0, VALIDATOR_1]);
+}
+
+#[test]
+fn test_init_with_invalid_validators() {
+    let mut chain = Chain::new();
+
+    chain.create_account(Account::new(ACC_ADDR_OWNER, ACC_INITIAL_BALANCE));
+
+    let deployment_staking_bank = chain
+        .module_deploy_v1(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            module_load_v1("../staking-bank/staking_bank.wasm.v1")
+                .expect("`staking_bank.wasm.v1` module should be loaded"),
+        )
+        .expect("`staking_bank.wasm.v1` deployment should always succeed");
+
+    // Checking that initializing without validators fails.
+
+    let init_params_without_validators = InitParamsStakingBank {
+        validators: vec![],
+        registry: REGISTRY,
+        liveness_window: LIVENESS_WINDOW,
+        unbonding_period: UNBONDING_PERIOD,
+    };
+
+    let init = chain.contract_init(
+        Signer::with_one_key(),
+        ACC_ADDR_OWNER,
+        Energy::from(10000),
+        InitContractPayload {
+            amount: Amount::zero(),
+            mod_ref: deployment_staking_bank.module_reference,
+            init_name: OwnedContractName::new_unchecked("init_staking_bank".to_string()),
+            param: OwnedParameter::from_serial(&init_params_without_validators)
+                .expect("`InitParamsStakingBank` should be a valid inut parameter"),
+        },
+    );
+
+    assert!(
+        init.is_err(),
+        "Initialization without validators should fail"
+    );
+
+    // Checking that initializing with a duplicated validator fails.
+
+    let init_params_with_duplicate = InitParamsStakingBank {
+        validators: vec![
+            (
+                VALIDATOR_0,
+                String::from("https://validator.dev.umb.network"),
+            ),
+            (
+                VALIDATOR_0,
+                String::from("https://validator2.dev.umb.network"),
+            ),
+        ],
+        registry: REGISTRY,
+        liveness_window: LIVENESS_WINDOW,
+        unbonding_period: UNBONDING_PERIOD,
+    };
+
+    let init = chain.contract_init(
+        Signer::with_one_key(),
+        ACC_ADDR_OWNER,
+        Energy::from(10000),
+        InitContractPayload {
+            amount: Amount::zero(),
+            mod_ref: deployment_staking_bank.module_reference,
+            init_name: OwnedContractName::new_unchecked("init_staking_bank".to_string()),
+            param: OwnedParameter::from_serial(&init_params_with_duplicate)
+                .expect("`InitParamsStakingBank` should be a valid inut parameter"),
+        },
+    );
+
+    assert!(
+        init.is_err(),
+        "Initialization with a duplicated validator should fail"
+    );
+
+    // Checking that initializing with a zero liveness window fails.
+
+    let init_params_with_zero_liveness_window = InitParamsStakingBank {
+        liveness_window: Duration::from_seconds(0),
+        ..init_params()
+    };
+
+    let init = chain.contract_init(
+        Signer::with_one_key(),
+        ACC_ADDR_OWNER,
+        Energy::from(10000),
+        InitContractPayload {
+            amount: Amount::zero(),
+            mod_ref: deployment_staking_bank.module_reference,
+            init_name: OwnedContractName::new_unchecked("init_staking_bank".to_string()),
+            param: OwnedParameter::from_serial(&init_params_with_zero_liveness_window)
+                .expect("`InitParamsStakingBank` should be a valid inut parameter"),
+        },
+    );
+
+    assert!(
+        init.is_err(),
+        "Initialization with a zero liveness window should fail"
+    );
+}
+
+#[test]
+fn test_liveness_and_jailing() {
+    let (mut chain, initialization_staking_bank) = setup_chain_and_contract();
+
+    chain.create_account(Account::new(ACC_ADDR_OTHER, ACC_INITIAL_BALANCE));
+
+    let staking_bank = initialization_staking_bank.contract_address;
+
+    // Checking that the validators are not jailed after initialization.
+
+    assert!(verify_validators(
+        &chain,
+        staking_bank,
+        vec![VALIDATOR_0, VALIDATOR_1]
+    ));
+
+    // Checking that the validators are not jailed after the liveness window passed without any participation report
+    // (validators are only jailed relative to the last report the staking bank received).
+
+    chain
+        .tick_block_time(Duration::from_seconds(7200))
+        .expect("Should be able to advance the block time");
+
+    assert!(verify_validators(
+        &chain,
+        staking_bank,
+        vec![VALIDATOR_0, VALIDATOR_1]
+    ));
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.validatorStatus".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&VALIDATOR_0)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query the validator status");
+
+    let value: ValidatorStatus =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        value,
+        ValidatorStatus {
+            last_participation: Timestamp::from_timestamp_millis(0),
+            jailed: false,
+        }
+    );
+
+    // Checking that reporting participation from an account fails.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.reportParticipation".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&vec![VALIDATOR_0])
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to report participation from an account");
+
+    assert_eq!(reject_reason(&update), Some(-9));
+
+    // Checking that only the owner can unjail a validator.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.unjail".to_string()),
+                message: OwnedParameter::from_serial(&VALIDATOR_0)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to unjail from a non-owner account");
+
+    assert_eq!(reject_reason(&update), Some(-9));
+
+    // Checking that a validator that is not jailed can not be unjailed (which would extend its liveness).
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.unjail".to_string()),
+                message: OwnedParameter::from_serial(&VALIDATOR_0)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to unjail a validator that is not jailed");
+
+    assert_eq!(reject_reason(&update), Some(-24));
+
+    let message = UnjailMessage {
+        contract_address: staking_bank,
+        timestamp: Timestamp::from_timestamp_millis(10000000000),
+        last_participation: Timestamp::from_timestamp_millis(0),
+    };
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.selfUnjail".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&SelfUnjailParams {
+                    public_key: VALIDATOR_0,
+                    signature: SIGNATURE_UNJAIL_VALIDATOR_1,
+                    message: message.clone(),
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to unjail with a signature of another validator");
+
+    assert_eq!(reject_reason(&update), Some(-13));
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.selfUnjail".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&SelfUnjailParams {
+                    public_key: VALIDATOR_1,
+                    signature: SIGNATURE_UNJAIL_VALIDATOR_1,
+                    message,
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to unjail itself when not jailed");
+
+    assert_eq!(reject_reason(&update), Some(-24));
+
+    // Checking that only the owner can set a non-zero liveness window.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.setLivenessWindow".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&Duration::from_seconds(60))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to set the liveness window from a non-owner account");
+
+    assert_eq!(reject_reason(&update), Some(-9));
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.setLivenessWindow".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&Duration::from_seconds(0))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to set a zero liveness window");
+
+    assert_eq!(reject_reason(&update), Some(-10));
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.setLivenessWindow".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&Duration::from_seconds(60))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to set the liveness window");
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.livenessWindow".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query the liveness window");
+
+    let value: Duration = from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(value, Duration::from_seconds(60));
+}
+
+#[test]
+fn test_delegation() {
+    let (mut chain, initialization_staking_bank) = setup_chain_and_contract();
+
+    chain.create_account(Account::new(ACC_ADDR_OTHER, ACC_INITIAL_BALANCE));
+
+    let staking_bank = initialization_staking_bank.contract_address;
+
+    // Checking that delegating to a public key that is not a validator fails.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::from_ccd(100),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.delegate".to_string()),
+                message: OwnedParameter::from_serial(&VALIDATOR_DOES_NOT_EXIST)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to delegate to a public key that is not a validator");
+
+    assert_eq!(reject_reason(&update), Some(-5));
+
+    // Checking that delegating zero CCD fails.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.delegate".to_string()),
+                message: OwnedParameter::from_serial(&VALIDATOR_0)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to delegate zero CCD");
+
+    assert_eq!(reject_reason(&update), Some(-15));
+
+    // Delegating 100 CCD to VALIDATOR_0.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::from_ccd(100),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.delegate".to_string()),
+                message: OwnedParameter::from_serial(&VALIDATOR_0)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to delegate");
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 2, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: DelegatedEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        DelegatedEvent {
+            delegator: ACC_ADDR_OTHER,
+            validator: VALIDATOR_0,
+            amount: Amount::from_ccd(100),
+            delegation: Amount::from_ccd(100),
+        },
+        "Delegated event is wrong"
+    );
+
+    // Checking that the delegated stake counts toward the balance of the validator.
+
+    assert_eq!(
+        balance_of(&chain, staking_bank, VALIDATOR_0),
+        ONE + 100_000_000
+    );
+    assert_eq!(balance_of(&chain, staking_bank, VALIDATOR_1), ONE);
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.totalSupply".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query value");
+
+    let value: u64 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(value, 2 * ONE + 100_000_000);
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.delegation".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&DelegationQuery {
+                    delegator: ACC_ADDR_OTHER,
+                    validator: VALIDATOR_0,
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query the delegation");
+
+    let value: Amount = from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(value, Amount::from_ccd(100));
+
+    // Checking that undelegating more than delegated fails.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.undelegate".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&UndelegateParams {
+                    validator: VALIDATOR_0,
+                    amount: Amount::from_ccd(150),
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to undelegate more than delegated");
+
+    assert_eq!(reject_reason(&update), Some(-16));
+
+    // Undelegating 40 CCD from VALIDATOR_0.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.undelegate".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&UndelegateParams {
+                    validator: VALIDATOR_0,
+                    amount: Amount::from_ccd(40),
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to undelegate");
+
+    let release_time = Timestamp::from_timestamp_millis(UNBONDING_PERIOD.millis());
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 3, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: UndelegatedEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        UndelegatedEvent {
+            delegator: ACC_ADDR_OTHER,
+            validator: VALIDATOR_0,
+            amount: Amount::from_ccd(40),
+            delegation: Amount::from_ccd(60),
+            release_time,
+        },
+        "Undelegated event is wrong"
+    );
+
+    // Checking the logged CIS-2 Burn event.
+    let event: Cis2Event<ContractTokenId, ContractTokenAmount> =
+        from_bytes(events[0].1[1].as_ref()).expect("Should be a CIS-2 event");
+
+    assert_eq!(
+        event,
+        Cis2Event::Burn(BurnEvent {
+            token_id: TokenIdFixed(VALIDATOR_0.0),
+            amount: TokenAmountU64(40_000_000),
+            owner: Address::Account(ACC_ADDR_OTHER),
+        }),
+        "Burn event is wrong"
+    );
+
+    assert_eq!(
+        balance_of(&chain, staking_bank, VALIDATOR_0),
+        ONE + 60_000_000
+    );
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.unbonding".to_string()),
+                message: OwnedParameter::from_serial(&ACC_ADDR_OTHER)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query the unbonding CCD");
+
+    let value: Vec<Unbonding> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        value,
+        vec![Unbonding {
+            amount: Amount::from_ccd(40),
+            release_time,
+        }]
+    );
+
+    // Undelegating another 10 CCD from VALIDATOR_0 halfway through the unbonding period. The 40 CCD keep their release
+    // time.
+
+    let half_unbonding_period = Duration::from_millis(UNBONDING_PERIOD.millis() / 2);
+
+    chain
+        .tick_block_time(half_unbonding_period)
+        .expect("Should be able to advance the block time");
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.undelegate".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&UndelegateParams {
+                    validator: VALIDATOR_0,
+                    amount: Amount::from_ccd(10),
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to undelegate");
+
+    let second_release_time = Timestamp::from_timestamp_millis(
+        half_unbonding_period.millis() + UNBONDING_PERIOD.millis(),
+    );
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.unbonding".to_string()),
+                message: OwnedParameter::from_serial(&ACC_ADDR_OTHER)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query the unbonding CCD");
+
+    let value: Vec<Unbonding> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        value,
+        vec![
+            Unbonding {
+                amount: Amount::from_ccd(40),
+                release_time,
+            },
+            Unbonding {
+                amount: Amount::from_ccd(10),
+                release_time: second_release_time,
+            }
+        ]
+    );
+
+    // Checking that withdrawing before the end of the unbonding period fails.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.withdraw".to_string()),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect_err("Should fail to withdraw during the unbonding period");
+
+    assert_eq!(reject_reason(&update), Some(-17));
+
+    // Withdrawing the 40 CCD after their unbonding period, the 10 CCD are still unbonding.
+
+    chain
+        .tick_block_time(half_unbonding_period)
+        .expect("Should be able to advance the block time");
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.withdraw".to_string()),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to withdraw");
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 4, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: WithdrawnEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        WithdrawnEvent {
+            delegator: ACC_ADDR_OTHER,
+            amount: Amount::from_ccd(40),
+        },
+        "Withdrawn event is wrong"
+    );
+
+    assert_eq!(
+        chain.contract_balance(staking_bank),
+        Some(Amount::from_ccd(60))
+    );
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.withdraw".to_string()),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect_err("Should fail to withdraw during the unbonding period");
+
+    assert_eq!(reject_reason(&update), Some(-17));
+
+    // Withdrawing the 10 CCD after their unbonding period.
+
+    chain
+        .tick_block_time(half_unbonding_period)
+        .expect("Should be able to advance the block time");
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.withdraw".to_string()),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to withdraw");
+
+    assert_eq!(
+        chain.contract_balance(staking_bank),
+        Some(Amount::from_ccd(50))
+    );
+}
+
+#[test]
+fn test_cis2() {
+    let (mut chain, initialization_staking_bank) = setup_chain_and_contract();
+
+    chain.create_account(Account::new(ACC_ADDR_OTHER, ACC_INITIAL_BALANCE));
+
+    let staking_bank = initialization_staking_bank.contract_address;
+
+    // Checking the CIS-2 TokenMetadata events logged at initialization.
+
+    let events: Vec<Cis2Event<ContractTokenId, ContractTokenAmount>> = initialization_staking_bank
+        .events
+        .iter()
+        .map(|event| from_bytes(event.as_ref()).expect("Should be a CIS-2 event"))
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![
+            Cis2Event::TokenMetadata(TokenMetadataEvent {
+                token_id: TokenIdFixed(VALIDATOR_0.0),
+                metadata_url: MetadataUrl {
+                    url: String::from("https://validator.dev.umb.network"),
+                    hash: None,
+                },
+            }),
+            Cis2Event::TokenMetadata(TokenMetadataEvent {
+                token_id: TokenIdFixed(VALIDATOR_1.0),
+                metadata_url: MetadataUrl {
+                    url: String::from("https://validator2.dev.umb.network"),
+                    hash: None,
+                },
+            }),
+        ]
+    );
+
+    // Checking supports.
+
+    let input_parameter = SupportsQueryParams {
+        queries: vec![
+            CIS0_STANDARD_IDENTIFIER.to_owned(),
+            CIS1_STANDARD_IDENTIFIER.to_owned(),
+            CIS2_STANDARD_IDENTIFIER.to_owned(),
+        ],
+    };
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.supports".to_string()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query supports");
+
+    let value: SupportsQueryResponse =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert!(matches!(
+        value.results[..],
+        [
+            SupportResult::Support,
+            SupportResult::NoSupport,
+            SupportResult::Support
+        ]
+    ));
+
+    // Delegating 100 CCD to VALIDATOR_0.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::from_ccd(100),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.delegate".to_string()),
+                message: OwnedParameter::from_serial(&VALIDATOR_0)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to delegate");
+
+    // Checking the logged CIS-2 Mint event (after the Delegated event).
+
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event: Cis2Event<ContractTokenId, ContractTokenAmount> =
+        from_bytes(events[0].1[1].as_ref()).expect("Should be a CIS-2 event");
+
+    assert_eq!(
+        event,
+        Cis2Event::Mint(MintEvent {
+            token_id: TokenIdFixed(VALIDATOR_0.0),
+            amount: TokenAmountU64(100_000_000),
+            owner: Address::Account(ACC_ADDR_OTHER),
+        }),
+        "Mint event is wrong"
+    );
+
+    // Checking balanceOf.
+
+    let input_parameter = ContractBalanceOfQueryParams {
+        queries: vec![
+            BalanceOfQuery {
+                token_id: TokenIdFixed(VALIDATOR_0.0),
+                address: Address::Account(ACC_ADDR_OTHER),
+            },
+            BalanceOfQuery {
+                token_id: TokenIdFixed(VALIDATOR_1.0),
+                address: Address::Account(ACC_ADDR_OTHER),
+            },
+            BalanceOfQuery {
+                token_id: TokenIdFixed(VALIDATOR_0.0),
+                address: Address::Account(ACC_ADDR_OWNER),
+            },
+            BalanceOfQuery {
+                token_id: TokenIdFixed(VALIDATOR_0.0),
+                address: Address::Contract(staking_bank),
+            },
+        ],
+    };
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.balanceOf".to_string()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query balanceOf");
+
+    let value: ContractBalanceOfQueryResponse =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        value.0,
+        vec![
+            TokenAmountU64(100_000_000),
+            TokenAmountU64(0),
+            TokenAmountU64(0),
+            TokenAmountU64(0)
+        ]
+    );
+
+    // Checking that querying the balance of a token that does not exist fails.
+
+    let input_parameter = ContractBalanceOfQueryParams {
+        queries: vec![BalanceOfQuery {
+            token_id: TokenIdFixed(VALIDATOR_DOES_NOT_EXIST.0),
+            address: Address::Account(ACC_ADDR_OTHER),
+        }],
+    };
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.balanceOf".to_string()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to query the balance of a token that does not exist");
+
+    assert_eq!(reject_reason(&invoke), Some(-42000001));
+
+    // Checking tokenMetadata.
+
+    let input_parameter = TokenMetadataQueryParams {
+        queries: vec![TokenIdFixed(VALIDATOR_1.0)],
+    };
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.tokenMetadata".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query tokenMetadata");
+
+    let value: TokenMetadataQueryResponse =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        value.0,
+        vec![MetadataUrl {
+            url: String::from("https://validator2.dev.umb.network"),
+            hash: None,
+        }]
+    );
+
+    // Checking that the token can not be transferred.
+
+    let input_parameter = ContractTransferParams::from(vec![Transfer {
+        token_id: TokenIdFixed(VALIDATOR_0.0),
+        amount: TokenAmountU64(1),
+        from: Address::Account(ACC_ADDR_OTHER),
+        to: Receiver::from_account(ACC_ADDR_OWNER),
+        data: AdditionalData::empty(),
+    }]);
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked("staking_bank.transfer".to_string()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to transfer");
+
+    assert_eq!(reject_reason(&update), Some(-42000003));
+
+    // Checking that operators are not supported.
+
+    let input_parameter = UpdateOperatorParams(vec![UpdateOperator {
+        update: OperatorUpdate::Add,
+        operator: Address::Account(ACC_ADDR_OWNER),
+    }]);
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.updateOperator".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to add an operator");
+
+    assert_eq!(reject_reason(&update), Some(-42000003));
+
+    let input_parameter = OperatorOfQueryParams {
+        queries: vec![OperatorOfQuery {
+            owner: Address::Account(ACC_ADDR_OTHER),
+            address: Address::Account(ACC_ADDR_OWNER),
+        }],
+    };
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.operatorOf".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query operatorOf");
+
+    let value: OperatorOfQueryResponse =
         from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    assert_eq!(value, [VALIDATOR_0, VALIDATOR_1]);
+    assert_eq!(value.0, vec![false]);
 }
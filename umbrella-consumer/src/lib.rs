@@ -0,0 +1,346 @@
+//! # Helper functions to read price data from the umbrella oracle protocol.
+//!
+//! The functions look up the `UmbrellaFeeds` contract in the registry contract of the umbrella oracle protocol, invoke
+//! it, parse its return value and check the heartbeat of the price data, so a consumer contract only needs the registry
+//! contract address:
+//!
+//! ```ignore
+//! let price_data = umbrella_consumer::fetch_fresh(host, &registry, "ETH-USDC", ctx.metadata().block_time())?;
+//! ```
+//!
+//! `fetch_with_fallback` tries an ordered list of sources (the primary `UmbrellaFeeds` contract, a secondary
+//! `UmbrellaFeeds` contract and the last known good price data of the consumer contract) and reports which source was
+//! used.
+//!
+//! All functions fail with a `ConsumerError`. Consumer contracts usually map it into their own error type with a
+//! `From<ConsumerError>` implementation. The `*_from` variants take the `UmbrellaFeeds` contract address directly
+//! (e.g. if the consumer contract caches it) instead of resolving it from the registry with every invoke.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use concordium_std::*;
+pub use umbrella_feeds::PriceData;
+
+/// The key name of the `UmbrellaFeeds` contract in the registry.
+pub const UMBRELLA_FEEDS_NAME: &str = "UmbrellaFeeds";
+
+/// Errors
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Reject, Serialize, SchemaType)]
+pub enum ConsumerError {
+    /// Failed parsing the return value of an invoked contract.
+    #[from(ParseError)]
+    ParseReturnValue, // -1
+    /// Failed to look up the `UmbrellaFeeds` contract in the registry.
+    InvokeRegistryError, // -2
+    /// Failed to invoke the `UmbrellaFeeds` contract (e.g. the price feed does not exist).
+    InvokeUmbrellaFeedsError, // -3
+    /// Failed because the price data is not up to date (its heartbeat elapsed).
+    PriceNotUpToDate, // -4
+    /// Failed because the timestamp or the scaled price overflowed.
+    Overflow, // -5
+}
+
+/// The source of the price data returned by `fetch_with_fallback`.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceSource {
+    /// The primary `UmbrellaFeeds` contract.
+    Primary,
+    /// The secondary `UmbrellaFeeds` contract.
+    Secondary,
+    /// The last known good price data of the consumer contract.
+    LastKnownGood,
+}
+
+/// The price data and its source returned by `fetch_with_fallback`.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourcedPriceData {
+    /// The price data.
+    pub price_data: PriceData,
+    /// The source of the price data.
+    pub source: PriceSource,
+}
+
+/// The sources of `fetch_with_fallback` in the order they are tried.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FallbackSources {
+    /// The primary `UmbrellaFeeds` contract, usually the one registered in the registry (`None` if it could not be
+    /// resolved).
+    pub primary: Option<ContractAddress>,
+    /// A secondary `UmbrellaFeeds` contract (`None` to skip this source).
+    pub secondary: Option<ContractAddress>,
+    /// The last known good price data stored by the consumer contract (`None` to skip this source).
+    pub last_known_good: Option<PriceData>,
+    /// The maximum age of the last known good price data (measured from its timestamp, zero disables this source).
+    pub max_age: Duration,
+}
+
+/// The parameter of the `getManyPriceData` entry point (serialized as a `Vec<String>`).
+struct Keys<'a>(&'a [String]);
+
+impl Serial for Keys<'_> {
+    fn serial<W: Write>(&self, out: &mut W) -> Result<(), W::Err> {
+        (self.0.len() as u32).serial(out)?;
+        serial_vector_no_length(self.0, out)
+    }
+}
+
+/// Queries the `UmbrellaFeeds` contract address from the registry.
+pub fn resolve_umbrella_feeds<St>(
+    host: &impl HasHost<St>,
+    registry: &ContractAddress,
+) -> Result<ContractAddress, ConsumerError> {
+    let umbrella_feeds = host
+        .invoke_contract_read_only(
+            registry,
+            &UMBRELLA_FEEDS_NAME,
+            EntrypointName::new_unchecked("getAddress"),
+            Amount::zero(),
+        )
+        .map_err(|_| ConsumerError::InvokeRegistryError)?;
+
+    let mut umbrella_feeds = umbrella_feeds.ok_or(ConsumerError::InvokeRegistryError)?;
+
+    Ok(umbrella_feeds.get()?)
+}
+
+/// Queries the price data of a price feed (such as `ETH-USDC`) from the `UmbrellaFeeds` contract registered in the
+/// registry. The price data is not checked to be up to date, use `fetch_fresh` for that.
+pub fn fetch_price<St>(
+    host: &impl HasHost<St>,
+    registry: &ContractAddress,
+    key: &str,
+) -> Result<PriceData, ConsumerError> {
+    let umbrella_feeds = resolve_umbrella_feeds(host, registry)?;
+
+    fetch_price_from(host, &umbrella_feeds, key)
+}
+
+/// Queries the price data of a price feed from the given `UmbrellaFeeds` contract.
+pub fn fetch_price_from<St>(
+    host: &impl HasHost<St>,
+    umbrella_feeds: &ContractAddress,
+    key: &str,
+) -> Result<PriceData, ConsumerError> {
+    let price_data = host
+        .invoke_contract_read_only(
+            umbrella_feeds,
+            &key,
+            EntrypointName::new_unchecked("getPriceData"),
+            Amount::zero(),
+        )
+        .map_err(|_| ConsumerError::InvokeUmbrellaFeedsError)?;
+
+    let mut price_data = price_data.ok_or(ConsumerError::InvokeUmbrellaFeedsError)?;
+
+    Ok(price_data.get()?)
+}
+
+/// Queries the price data of several price feeds with one invoke of the `UmbrellaFeeds` contract registered in the
+/// registry. The price data is returned in the order of the keys. Fails if any of the price feeds does not exist.
+pub fn fetch_many<St>(
+    host: &impl HasHost<St>,
+    registry: &ContractAddress,
+    keys: &[String],
+) -> Result<Vec<PriceData>, ConsumerError> {
+    let umbrella_feeds = resolve_umbrella_feeds(host, registry)?;
+
+    fetch_many_from(host, &umbrella_feeds, keys)
+}
+
+/// Queries the price data of several price feeds from the given `UmbrellaFeeds` contract.
+pub fn fetch_many_from<St>(
+    host: &impl HasHost<St>,
+    umbrella_feeds: &ContractAddress,
+    keys: &[String],
+) -> Result<Vec<PriceData>, ConsumerError> {
+    let price_data = host
+        .invoke_contract_read_only(
+            umbrella_feeds,
+            &Keys(keys),
+            EntrypointName::new_unchecked("getManyPriceData"),
+            Amount::zero(),
+        )
+        .map_err(|_| ConsumerError::InvokeUmbrellaFeedsError)?;
+
+    let mut price_data = price_data.ok_or(ConsumerError::InvokeUmbrellaFeedsError)?;
+
+    Ok(price_data.get()?)
+}
+
+/// Queries the price data of a price feed from the `UmbrellaFeeds` contract registered in the registry and checks that
+/// it is up to date at `now` (usually the block time).
+pub fn fetch_fresh<St>(
+    host: &impl HasHost<St>,
+    registry: &ContractAddress,
+    key: &str,
+    now: Timestamp,
+) -> Result<PriceData, ConsumerError> {
+    let umbrella_feeds = resolve_umbrella_feeds(host, registry)?;
+
+    fetch_fresh_from(host, &umbrella_feeds, key, now)
+}
+
+/// Queries the price data of a price feed from the given `UmbrellaFeeds` contract and checks that it is up to date at
+/// `now`.
+pub fn fetch_fresh_from<St>(
+    host: &impl HasHost<St>,
+    umbrella_feeds: &ContractAddress,
+    key: &str,
+    now: Timestamp,
+) -> Result<PriceData, ConsumerError> {
+    let price_data = fetch_price_from(host, umbrella_feeds, key)?;
+
+    ensure_fresh(&price_data, now)?;
+
+    Ok(price_data)
+}
+
+/// Queries up-to-date price data of a price feed from the primary and then the secondary `UmbrellaFeeds` contract and
+/// falls back to the last known good price data if it is younger than the `max_age` at `now`. Returns the price data
+/// of the first available source together with the source. If no source is available, the error of the primary
+/// `UmbrellaFeeds` contract is returned.
+///
+/// The consumer contract usually stores the returned price data as its new last known good price data unless it came
+/// from the `LastKnownGood` source.
+pub fn fetch_with_fallback<St>(
+    host: &impl HasHost<St>,
+    sources: &FallbackSources,
+    key: &str,
+    now: Timestamp,
+) -> Result<SourcedPriceData, ConsumerError> {
+    let primary = match sources.primary {
+        Some(primary) => fetch_fresh_from(host, &primary, key, now),
+        None => Err(ConsumerError::InvokeUmbrellaFeedsError),
+    };
+
+    let error = match primary {
+        Ok(price_data) => {
+            return Ok(SourcedPriceData {
+                price_data,
+                source: PriceSource::Primary,
+            })
+        }
+        Err(error) => error,
+    };
+
+    if let Some(secondary) = sources.secondary {
+        if let Ok(price_data) = fetch_fresh_from(host, &secondary, key, now) {
+            return Ok(SourcedPriceData {
+                price_data,
+                source: PriceSource::Secondary,
+            });
+        }
+    }
+
+    if let Some(price_data) = sources.last_known_good {
+        if ensure_max_age(&price_data, now, sources.max_age).is_ok() {
+            return Ok(SourcedPriceData {
+                price_data,
+                source: PriceSource::LastKnownGood,
+            });
+        }
+    }
+
+    Err(error)
+}
+
+/// Queries the up-to-date price of a price feed from the `UmbrellaFeeds` contract registered in the registry and scales
+/// it from the decimals of the `UmbrellaFeeds` contract to the given `decimals`.
+pub fn fetch_scaled<St>(
+    host: &impl HasHost<St>,
+    registry: &ContractAddress,
+    key: &str,
+    now: Timestamp,
+    decimals: u8,
+) -> Result<u128, ConsumerError> {
+    let umbrella_feeds = resolve_umbrella_feeds(host, registry)?;
+
+    fetch_scaled_from(host, &umbrella_feeds, key, now, decimals)
+}
+
+/// Queries the up-to-date price of a price feed from the given `UmbrellaFeeds` contract and scales it to the given
+/// `decimals`.
+pub fn fetch_scaled_from<St>(
+    host: &impl HasHost<St>,
+    umbrella_feeds: &ContractAddress,
+    key: &str,
+    now: Timestamp,
+    decimals: u8,
+) -> Result<u128, ConsumerError> {
+    let price_data = fetch_fresh_from(host, umbrella_feeds, key, now)?;
+
+    let umbrella_decimals = fetch_decimals_from(host, umbrella_feeds)?;
+
+    scale_price(price_data.price, umbrella_decimals, decimals)
+}
+
+/// Queries the decimals of the prices in the given `UmbrellaFeeds` contract.
+pub fn fetch_decimals_from<St>(
+    host: &impl HasHost<St>,
+    umbrella_feeds: &ContractAddress,
+) -> Result<u8, ConsumerError> {
+    let decimals = host
+        .invoke_contract_read_only(
+            umbrella_feeds,
+            &(),
+            EntrypointName::new_unchecked("DECIMALS"),
+            Amount::zero(),
+        )
+        .map_err(|_| ConsumerError::InvokeUmbrellaFeedsError)?;
+
+    let mut decimals = decimals.ok_or(ConsumerError::InvokeUmbrellaFeedsError)?;
+
+    Ok(decimals.get()?)
+}
+
+/// Checks that the price data is up to date at `now`, i.e. that its heartbeat did not elapse since its timestamp.
+pub fn ensure_fresh(price_data: &PriceData, now: Timestamp) -> Result<(), ConsumerError> {
+    let heartbeat = price_data
+        .heartbeat
+        .checked_mul(1000)
+        .map(Duration::from_millis)
+        .ok_or(ConsumerError::Overflow)?;
+
+    ensure!(
+        now < price_data
+            .timestamp
+            .checked_add(heartbeat)
+            .ok_or(ConsumerError::Overflow)?,
+        ConsumerError::PriceNotUpToDate
+    );
+
+    Ok(())
+}
+
+/// Checks that the price data is younger than `max_age` at `now` (regardless of its heartbeat).
+pub fn ensure_max_age(
+    price_data: &PriceData,
+    now: Timestamp,
+    max_age: Duration,
+) -> Result<(), ConsumerError> {
+    ensure!(
+        now < price_data
+            .timestamp
+            .checked_add(max_age)
+            .ok_or(ConsumerError::Overflow)?,
+        ConsumerError::PriceNotUpToDate
+    );
+
+    Ok(())
+}
+
+/// Scales a price from `from_decimals` to `to_decimals`. Scaling down rounds towards zero.
+pub fn scale_price(price: u128, from_decimals: u8, to_decimals: u8) -> Result<u128, ConsumerError> {
+    if to_decimals >= from_decimals {
+        let factor = 10u128
+            .checked_pow(u32::from(to_decimals - from_decimals))
+            .ok_or(ConsumerError::Overflow)?;
+
+        price.checked_mul(factor).ok_or(ConsumerError::Overflow)
+    } else {
+        match 10u128.checked_pow(u32::from(from_decimals - to_decimals)) {
+            Some(factor) => Ok(price / factor),
+            // The factor is larger than any price.
+            None => Ok(0),
+        }
+    }
+}
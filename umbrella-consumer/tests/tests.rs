@@ -0,0 +1,87 @@
+//! Tests for the umbrella consumer helper functions. Reading price data from the umbrella oracle is tested by the
+//! oracle integration example.
+use concordium_std::{Duration, Timestamp};
+use umbrella_consumer::{ensure_fresh, ensure_max_age, scale_price, ConsumerError, PriceData};
+
+/// Test that price data is only fresh until its heartbeat elapsed.
+#[test]
+fn test_ensure_fresh() {
+    let price_data = PriceData {
+        data: 0,
+        heartbeat: 60,
+        timestamp: Timestamp::from_timestamp_millis(1_000_000),
+        price: 7,
+    };
+
+    assert_eq!(ensure_fresh(&price_data, price_data.timestamp), Ok(()));
+
+    let almost_stale = price_data
+        .timestamp
+        .checked_add(Duration::from_millis(59_999))
+        .unwrap();
+    assert_eq!(ensure_fresh(&price_data, almost_stale), Ok(()));
+
+    let stale = price_data
+        .timestamp
+        .checked_add(Duration::from_seconds(60))
+        .unwrap();
+    assert_eq!(
+        ensure_fresh(&price_data, stale),
+        Err(ConsumerError::PriceNotUpToDate)
+    );
+
+    let overflowing = PriceData {
+        heartbeat: u64::MAX,
+        ..price_data
+    };
+    assert_eq!(
+        ensure_fresh(&overflowing, stale),
+        Err(ConsumerError::Overflow)
+    );
+}
+
+/// Test that the last known good price data is only used until its maximum age elapsed, regardless of its heartbeat.
+#[test]
+fn test_ensure_max_age() {
+    let price_data = PriceData {
+        data: 0,
+        heartbeat: 60,
+        timestamp: Timestamp::from_timestamp_millis(1_000_000),
+        price: 7,
+    };
+
+    let stale = price_data
+        .timestamp
+        .checked_add(Duration::from_seconds(60))
+        .unwrap();
+
+    assert_eq!(
+        ensure_max_age(&price_data, stale, Duration::from_seconds(3600)),
+        Ok(())
+    );
+    assert_eq!(
+        ensure_max_age(&price_data, stale, Duration::from_seconds(60)),
+        Err(ConsumerError::PriceNotUpToDate)
+    );
+
+    // A maximum age of zero disables the last known good price data.
+    assert_eq!(
+        ensure_max_age(&price_data, price_data.timestamp, Duration::from_millis(0)),
+        Err(ConsumerError::PriceNotUpToDate)
+    );
+}
+
+/// Test scaling prices between decimals.
+#[test]
+fn test_scale_price() {
+    // 3500.80 with 8 decimals.
+    let price = 350_080_000_000;
+
+    assert_eq!(scale_price(price, 8, 8), Ok(price));
+    assert_eq!(scale_price(price, 8, 18), Ok(3_500_800_000_000_000_000_000));
+    assert_eq!(scale_price(price, 8, 1), Ok(35_008));
+    assert_eq!(scale_price(price, 8, 0), Ok(3_500));
+    assert_eq!(scale_price(price, 255, 0), Ok(0));
+    assert_eq!(scale_price(price, 8, 38), Err(ConsumerError::Overflow));
+    assert_eq!(scale_price(price, 0, 255), Err(ConsumerError::Overflow));
+}
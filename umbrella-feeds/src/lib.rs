@@ -83,8 +83,29 @@ enum CustomContractError {
     NotEnoughSignatures, // -17
     /// Failed because the signatures are not in order.
     SignaturesOutOfOrder, // -18
-    /// Failed because one of the given signers is not a validator.
-    InvalidSigner, // -19
+    /// Failed because one of the given signers is not a validator. The public key of the offending signer is part
+    /// of the return value.
+    InvalidSigner(PublicKeyEd25519), // -19
+}
+
+/// Tagged events to be serialized for the event log.
+#[derive(Debug, Serial, SchemaType)]
+#[concordium(repr(u8))]
+enum Event {
+    /// The event tracks whenever the participation of the validators in a price update could not be reported to the
+    /// staking bank.
+    #[concordium(tag = 0)]
+    ParticipationReportFailed(ParticipationReportFailedEvent),
+}
+
+/// The ParticipationReportFailedEvent is logged when the `reportParticipation` invoke to the staking bank fails (e.g.
+/// because this contract is not the `UmbrellaFeeds` contract registered in the registry).
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ParticipationReportFailedEvent {
+    /// The staking bank that rejected the report.
+    pub staking_bank: ContractAddress,
+    /// The validators whose participation could not be reported.
+    pub participants: Vec<PublicKeyEd25519>,
 }
 
 /// Mapping errors related to logging to CustomContractError.
@@ -136,7 +157,11 @@ pub struct InitParamsUmbrellaFeeds {
 }
 
 /// Init function that creates a new smart contract.
-#[init(contract = "umbrella_feeds", parameter = "InitParamsUmbrellaFeeds")]
+#[init(
+    contract = "umbrella_feeds",
+    parameter = "InitParamsUmbrellaFeeds",
+    event = "Event"
+)]
 fn init<S: HasStateApi>(
     ctx: &impl HasInitContext,
     state_builder: &mut StateBuilder<S>,
@@ -306,6 +331,16 @@ pub struct UpdateParamsPartial {
     pub signers_and_signatures: Vec<(PublicKeyEd25519, SignatureEd25519)>,
 }
 
+/// The return_value type of the `verifyValidatorsDetailed` entry point in the `staking_bank` contract.
+#[derive(Deserial)]
+struct VerifyValidatorsResult {
+    /// The index (in the provided list) and the public key of every provided key that is not a validator or is jailed.
+    invalid_validators: Vec<(u32, PublicKeyEd25519)>,
+    /// The total balance of the provided keys that are validators and not jailed.
+    #[allow(dead_code)]
+    valid_balance: u64,
+}
+
 /// Helper function to calculate the `message_hash`.
 #[receive(
     contract = "umbrella_feeds",
@@ -393,18 +428,20 @@ fn verify_signatures<S: HasStateApi>(
         prev_signer = Some(signer);
     }
 
-    let are_valid_signers = host.invoke_contract_read_only::<Vec<PublicKeyEd25519>>(
+    let verification = host.invoke_contract_read_only::<Vec<PublicKeyEd25519>>(
         &host.state().staking_bank,
         &validators,
-        EntrypointName::new_unchecked("verifyValidators"),
+        EntrypointName::new_unchecked("verifyValidatorsDetailed"),
         Amount::zero(),
     )?;
 
-    let are_valid_signers: bool = are_valid_signers
+    let verification: VerifyValidatorsResult = verification
         .ok_or(CustomContractError::InvokeContractError)?
         .get()?;
 
-    ensure!(are_valid_signers, CustomContractError::InvalidSigner);
+    if let Some((_index, signer)) = verification.invalid_validators.first() {
+        return Err(CustomContractError::InvalidSigner(*signer));
+    }
 
     Ok(())
 }
@@ -415,11 +452,13 @@ fn verify_signatures<S: HasStateApi>(
     parameter = "UpdateParams",
     error = "CustomContractError",
     crypto_primitives,
+    enable_logger,
     mutable
 )]
 fn update<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
     crypto_primitives: &impl HasCryptoPrimitives,
 ) -> Result<(), CustomContractError> {
     let param: UpdateParams = ctx.parameter_cursor().get()?;
@@ -463,6 +502,38 @@ fn update<S: HasStateApi>(
         }
     }
 
+    // Report the validators whose signatures were verified (the first `required_signatures` signers) to the staking bank
+    // so it can track their liveness. The report is best-effort: the staking bank only accepts it from the `UmbrellaFeeds`
+    // contract registered in the registry, and a failed report must not prevent other instances (e.g. a secondary
+    // contract or a contract that is not imported into the registry yet) from updating their prices. A failed report is
+    // logged instead. The staking bank only jails validators relative to the last report it received, so undelivered
+    // reports never jail validators.
+    let required_signatures = host.state().required_signatures as usize;
+
+    let participants: Vec<PublicKeyEd25519> = param.signers_and_signatures[..required_signatures]
+        .iter()
+        .map(|(signer, _signature)| *signer)
+        .collect();
+
+    let staking_bank = host.state().staking_bank;
+
+    let report = host.invoke_contract(
+        &staking_bank,
+        &participants,
+        EntrypointName::new_unchecked("reportParticipation"),
+        Amount::zero(),
+    );
+
+    if report.is_err() {
+        // Log ParticipationReportFailed event
+        logger.log(&Event::ParticipationReportFailed(
+            ParticipationReportFailedEvent {
+                staking_bank,
+                participants,
+            },
+        ))?;
+    }
+
     Ok(())
 }
 
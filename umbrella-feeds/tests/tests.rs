@@ -4,11 +4,14 @@ use concordium_smart_contract_testing::*;
 use concordium_std::HashSha2256;
 use concordium_std::{CredentialSignatures, PublicKeyEd25519, SignatureEd25519, Timestamp};
 use registry::{
-    AtomicUpdateParam, ImportAddressesParam, ImportAddressesParams, ImportContractsParam,
+    AtomicUpdateManyParams, AtomicUpdateParam, ContractUpgradedEvent, HistoryEntry,
+    ImportAddressesParam, ImportAddressesParams, ImportContractsParam, RemoveAddressesParams,
+    RollbackParams,
 };
+use staking_bank::{InitParamsStakingBank, SelfUnjailParams, UnjailMessage, ValidatorStatus};
 use umbrella_feeds::{
-    ContractSetup, InitParamsUmbrellaFeeds, Message, PriceData, SchemTypeTripleWrapper,
-    UpdateParams,
+    ContractSetup, InitParamsUmbrellaFeeds, Message, ParticipationReportFailedEvent, PriceData,
+    SchemTypeTripleWrapper, UpdateParams,
 };
 
 const ACC_ADDR_OWNER: AccountAddress = AccountAddress([0u8; 32]);
@@ -68,11 +71,175 @@ const PUBLIC_KEY_SIGNER_2: [u8; 32] = [
     243, 162, 209, 78, 136, 94, 127, 247, 21, 222, 221,
 ];
 
+// Private key: 3C9A5E1F7B2D4806A1E3C5B7D9F0214365879ABCDEF0123456789ABCDEF01234
+const PUBLIC_KEY_VALIDATOR_3: [u8; 32] = [
+    11, 165, 241, 56, 10, 112, 108, 178, 82, 154, 171, 163, 131, 106, 108, 43, 34, 230, 3, 48, 129,
+    110, 173, 0, 100, 239, 120, 147, 189, 164, 122, 71,
+];
+
+// A validator that never signs (no private key needed).
+const PUBLIC_KEY_VALIDATOR_4: [u8; 32] = [4u8; 32];
+
+// Signature of VALIDATOR_3 on the hash of the `UnjailMessage` for the staking bank contract <1,0> with the timestamp
+// 10000000000 and the last participation 0.
+const SIGNATURE_UNJAIL_VALIDATOR_3: SignatureEd25519 = SignatureEd25519([
+    30, 9, 56, 198, 126, 18, 143, 245, 183, 174, 73, 122, 196, 104, 83, 128, 213, 139, 101, 244,
+    70, 210, 35, 248, 252, 167, 103, 196, 199, 92, 24, 61, 39, 228, 95, 212, 72, 237, 215, 181,
+    170, 57, 218, 140, 128, 6, 18, 119, 111, 176, 166, 251, 226, 202, 53, 249, 156, 195, 203, 41,
+    234, 139, 255, 9,
+]);
+
+/// Returns the reject reason of a failed invoke if the contract rejected on its own.
+fn reject_reason(error: &ContractInvokeError) -> Option<i32> {
+    match &error.kind {
+        ContractInvokeErrorKind::ExecutionError {
+            failure_kind: InvokeFailure::ContractReject { code, .. },
+        } => Some(*code),
+        _ => None,
+    }
+}
+
+/// Invokes an entry point of the registry as the owner.
+fn update_registry<P: concordium_std::Serial>(
+    chain: &mut Chain,
+    registry: ContractAddress,
+    entrypoint: &str,
+    parameter: &P,
+) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+    chain.contract_update(
+        Signer::with_one_key(),
+        ACC_ADDR_OWNER,
+        Address::Account(ACC_ADDR_OWNER),
+        Energy::from(100000),
+        UpdateContractPayload {
+            address: registry,
+            receive_name: OwnedReceiveName::new_unchecked(format!("registry.{entrypoint}")),
+            message: OwnedParameter::from_serial(parameter)
+                .expect("Should be a valid inut parameter"),
+            amount: Amount::from_ccd(0),
+        },
+    )
+}
+
+/// Invokes `getHistory` of the `UmbrellaFeeds` name in the registry.
+fn get_registry_history(chain: &Chain, registry: ContractAddress) -> Vec<HistoryEntry> {
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked("registry.getHistory".to_string()),
+                message: OwnedParameter::from_serial(&String::from("UmbrellaFeeds"))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query the history");
+
+    from_bytes(&invoke.return_value).expect("Should return a valid result")
+}
+
+/// Invokes `getPreviousModules` of a contract in the registry.
+fn get_previous_modules(
+    chain: &Chain,
+    registry: ContractAddress,
+    contract_address: ContractAddress,
+) -> Vec<ModuleReference> {
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: registry,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "registry.getPreviousModules".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&contract_address)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query the previous modules");
+
+    from_bytes(&invoke.return_value).expect("Should return a valid result")
+}
+
+/// Invokes an entry point of the staking bank as the owner.
+fn update_staking_bank<P: concordium_std::Serial>(
+    chain: &mut Chain,
+    staking_bank: ContractAddress,
+    entrypoint: &str,
+    parameter: &P,
+) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+    chain.contract_update(
+        Signer::with_one_key(),
+        ACC_ADDR_OWNER,
+        Address::Account(ACC_ADDR_OWNER),
+        Energy::from(100000),
+        UpdateContractPayload {
+            address: staking_bank,
+            receive_name: OwnedReceiveName::new_unchecked(format!("staking_bank.{entrypoint}")),
+            message: OwnedParameter::from_serial(parameter)
+                .expect("Should be a valid inut parameter"),
+            amount: Amount::from_ccd(0),
+        },
+    )
+}
+
+/// Invokes `validatorStatus` of a validator in the staking bank.
+fn validator_status(
+    chain: &Chain,
+    staking_bank: ContractAddress,
+    key: [u8; 32],
+) -> ValidatorStatus {
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.validatorStatus".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&PublicKeyEd25519(key))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query the validator status");
+
+    from_bytes(&invoke.return_value).expect("Should return a valid result")
+}
+
 fn setup_chain_and_contract() -> (
     Chain,
     ContractInitSuccess,
     ContractInitSuccess,
     ContractInitSuccess,
+) {
+    setup_chain_and_contract_with_validators(vec![
+        (
+            PublicKeyEd25519(PUBLIC_KEY_SIGNER_1),
+            String::from("https://validator.dev.umb.network"),
+        ),
+        (
+            PublicKeyEd25519(PUBLIC_KEY_SIGNER_2),
+            String::from("https://validator2.dev.umb.network"),
+        ),
+    ])
+}
+
+fn setup_chain_and_contract_with_validators(
+    validators: Vec<(PublicKeyEd25519, String)>,
+) -> (
+    Chain,
+    ContractInitSuccess,
+    ContractInitSuccess,
+    ContractInitSuccess,
 ) {
     let mut chain = Chain::new();
 
@@ -116,6 +283,13 @@ fn setup_chain_and_contract() -> (
         )
         .expect("`staking_bank.wasm.v1` deployment should always succeed");
 
+    let input_parameter = InitParamsStakingBank {
+        validators,
+        registry: initialization_registry.contract_address,
+        liveness_window: Duration::from_seconds(3600),
+        unbonding_period: Duration::from_seconds(86400),
+    };
+
     let initialization_staking_bank = chain
         .contract_init(
             Signer::with_one_key(),
@@ -125,7 +299,8 @@ fn setup_chain_and_contract() -> (
                 amount: Amount::zero(),
                 mod_ref: deployment_staking_bank.module_reference,
                 init_name: OwnedContractName::new_unchecked("init_staking_bank".to_string()),
-                param: OwnedParameter::empty(),
+                param: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`InitParamsStakingBank` should be a valid inut parameter"),
             },
         )
         .expect("Initialization of `staking_bank` should always succeed");
@@ -163,6 +338,28 @@ fn setup_chain_and_contract() -> (
         )
         .expect("Initialization of `umbrella_feeds` should always succeed");
 
+    // Importing umbrella_feeds into the registry contract so the staking bank accepts its participation reports
+
+    let input_parameter = ImportContractsParam {
+        entries: vec![initialization_umbrella_feeds.contract_address],
+    };
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.importContracts".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to import umbrella_feeds into the registry");
+
     (
         chain,
         initialization_umbrella_feeds,
@@ -865,6 +1062,386 @@ fn test_update_price_feed_and_check_price_via_feed_name() {
     assert_eq!(stored_price_data, price_data);
 }
 
+#[test]
+fn test_update_price_feed_reports_participation() {
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        initialization_staking_bank,
+    ) = setup_chain_and_contract_with_validators(
+        [
+            PUBLIC_KEY_SIGNER_1,
+            PUBLIC_KEY_SIGNER_2,
+            PUBLIC_KEY_VALIDATOR_3,
+            PUBLIC_KEY_VALIDATOR_4,
+        ]
+        .into_iter()
+        .map(|key| {
+            (
+                PublicKeyEd25519(key),
+                String::from("https://validator.dev.umb.network"),
+            )
+        })
+        .collect(),
+    );
+
+    let staking_bank = initialization_staking_bank.contract_address;
+
+    // Shortening the liveness window, so the validators that did not sign are jailed by the next report.
+
+    update_staking_bank(
+        &mut chain,
+        staking_bank,
+        "setLivenessWindow",
+        &Duration::from_seconds(60),
+    )
+    .expect("Should be able to set the liveness window");
+
+    chain
+        .tick_block_time(Duration::from_seconds(1800))
+        .expect("Should be able to advance the block time");
+
+    // Checking that the validators are not jailed before a participation report was received.
+
+    for key in [PUBLIC_KEY_VALIDATOR_3, PUBLIC_KEY_VALIDATOR_4] {
+        assert!(!validator_status(&chain, staking_bank, key).jailed);
+    }
+
+    let price_data = PriceData {
+        data: 7,
+        heartbeat: 12,
+        timestamp: Timestamp::from_timestamp_millis(9),
+        price: 4,
+    };
+
+    let update_param = UpdateParams {
+        signers_and_signatures: vec![
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_1), SIGNATURE_1),
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_2), SIGNATURE_2),
+        ],
+        message: Message {
+            timestamp: Timestamp::from_timestamp_millis(10000000000),
+            contract_address: initialization_umbrella_feeds.contract_address,
+            price_feed: vec![(String::from("Contract1"), price_data)],
+        },
+    };
+
+    // Updating price data in contract
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to update the price data");
+
+    // Checking that no `ParticipationReportFailed` event was logged.
+
+    assert!(update.events().all(|(address, events)| address
+        != initialization_umbrella_feeds.contract_address
+        || events.is_empty()));
+
+    // Checking that the participation of the signers was reported to the staking bank.
+
+    for signer in [PUBLIC_KEY_SIGNER_1, PUBLIC_KEY_SIGNER_2] {
+        assert_eq!(
+            validator_status(&chain, staking_bank, signer),
+            ValidatorStatus {
+                last_participation: Timestamp::from_timestamp_millis(1800000),
+                jailed: false,
+            }
+        );
+    }
+
+    // Checking that the validators that did not sign are jailed by the report.
+
+    for key in [PUBLIC_KEY_VALIDATOR_3, PUBLIC_KEY_VALIDATOR_4] {
+        assert_eq!(
+            validator_status(&chain, staking_bank, key),
+            ValidatorStatus {
+                last_participation: Timestamp::from_timestamp_millis(0),
+                jailed: true,
+            }
+        );
+    }
+
+    // Checking that the owner can unjail a validator, but not a validator that is not jailed.
+
+    update_staking_bank(
+        &mut chain,
+        staking_bank,
+        "unjail",
+        &PublicKeyEd25519(PUBLIC_KEY_VALIDATOR_4),
+    )
+    .expect("Should be able to unjail a validator");
+
+    assert!(!validator_status(&chain, staking_bank, PUBLIC_KEY_VALIDATOR_4).jailed);
+
+    let update = update_staking_bank(
+        &mut chain,
+        staking_bank,
+        "unjail",
+        &PublicKeyEd25519(PUBLIC_KEY_VALIDATOR_4),
+    )
+    .expect_err("Should fail to unjail a validator that is not jailed");
+
+    assert_eq!(reject_reason(&update), Some(-24));
+
+    // Checking that a validator can unjail itself with a signature of its validator key.
+
+    let self_unjail_params = SelfUnjailParams {
+        public_key: PublicKeyEd25519(PUBLIC_KEY_VALIDATOR_3),
+        signature: SIGNATURE_UNJAIL_VALIDATOR_3,
+        message: UnjailMessage {
+            contract_address: staking_bank,
+            timestamp: Timestamp::from_timestamp_millis(10000000000),
+            last_participation: Timestamp::from_timestamp_millis(0),
+        },
+    };
+
+    update_staking_bank(&mut chain, staking_bank, "selfUnjail", &self_unjail_params)
+        .expect("Should be able to unjail itself");
+
+    assert_eq!(
+        validator_status(&chain, staking_bank, PUBLIC_KEY_VALIDATOR_3),
+        ValidatorStatus {
+            last_participation: Timestamp::from_timestamp_millis(1800000),
+            jailed: false,
+        }
+    );
+
+    // Checking that the signature can not be replayed once the last participation changed.
+
+    let update = update_staking_bank(&mut chain, staking_bank, "selfUnjail", &self_unjail_params)
+        .expect_err("Should fail to unjail with a used signature");
+
+    assert_eq!(reject_reason(&update), Some(-23));
+}
+
+#[test]
+fn test_update_price_feed_without_registration() {
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        initialization_registry,
+        initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    let staking_bank = initialization_staking_bank.contract_address;
+
+    // Removing umbrella_feeds from the registry, so the staking bank rejects its participation reports.
+
+    let input_parameter = RemoveAddressesParams {
+        names: vec![String::from("UmbrellaFeeds")],
+        unregister: false,
+    };
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.removeAddresses".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to remove umbrella_feeds from the registry");
+
+    update_staking_bank(
+        &mut chain,
+        staking_bank,
+        "setLivenessWindow",
+        &Duration::from_seconds(60),
+    )
+    .expect("Should be able to set the liveness window");
+
+    chain
+        .tick_block_time(Duration::from_seconds(1800))
+        .expect("Should be able to advance the block time");
+
+    let price_data = PriceData {
+        data: 7,
+        heartbeat: 12,
+        timestamp: Timestamp::from_timestamp_millis(9),
+        price: 4,
+    };
+
+    let update_param = UpdateParams {
+        signers_and_signatures: vec![
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_1), SIGNATURE_1),
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_2), SIGNATURE_2),
+        ],
+        message: Message {
+            timestamp: Timestamp::from_timestamp_millis(10000000000),
+            contract_address: initialization_umbrella_feeds.contract_address,
+            price_feed: vec![(String::from("Contract1"), price_data)],
+        },
+    };
+
+    // Updating price data in contract (the failed participation report does not revert the update)
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to update the price data");
+
+    // Checking the logged `ParticipationReportFailed` event.
+
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let (_, umbrella_feeds_events) = events
+        .iter()
+        .rev()
+        .find(|(address, _)| *address == initialization_umbrella_feeds.contract_address)
+        .expect("The umbrella_feeds contract should log events");
+
+    let event = &umbrella_feeds_events[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 0, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: ParticipationReportFailedEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        ParticipationReportFailedEvent {
+            staking_bank,
+            participants: vec![
+                PublicKeyEd25519(PUBLIC_KEY_SIGNER_1),
+                PublicKeyEd25519(PUBLIC_KEY_SIGNER_2)
+            ],
+        },
+        "ParticipationReportFailed event is wrong"
+    );
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.getPriceData".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&String::from("Contract1"))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query prices");
+
+    let stored_price_data: PriceData =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(stored_price_data, price_data);
+
+    // Checking that the participation was not recorded by the staking bank and that the validators are not jailed,
+    // although the liveness window passed (no report was received).
+
+    assert_eq!(
+        validator_status(&chain, staking_bank, PUBLIC_KEY_SIGNER_1),
+        ValidatorStatus {
+            last_participation: Timestamp::from_timestamp_millis(0),
+            jailed: false,
+        }
+    );
+}
+
+#[test]
+fn test_update_price_feed_with_invalid_signer() {
+    // Only `signer_1` is a validator in the `staking_bank` contract.
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract_with_validators(vec![(
+        PublicKeyEd25519(PUBLIC_KEY_SIGNER_1),
+        String::from("https://validator.dev.umb.network"),
+    )]);
+
+    let price_data = PriceData {
+        data: 7,
+        heartbeat: 12,
+        timestamp: Timestamp::from_timestamp_millis(9),
+        price: 4,
+    };
+
+    let update_param = UpdateParams {
+        signers_and_signatures: vec![
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_1), SIGNATURE_1),
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_2), SIGNATURE_2),
+        ],
+        message: Message {
+            timestamp: Timestamp::from_timestamp_millis(10000000000),
+            contract_address: initialization_umbrella_feeds.contract_address,
+            price_feed: vec![(String::from("Contract1"), price_data)],
+        },
+    };
+
+    // Updating price data in contract
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail because `signer_2` is not a validator");
+
+    // Checking that the error is `InvalidSigner` (-19) and that it contains the offending signer.
+
+    match update.kind {
+        ContractInvokeErrorKind::ExecutionError {
+            failure_kind: InvokeFailure::ContractReject { code, data },
+        } => {
+            assert_eq!(code, -19);
+
+            let (_tag, signer): (u8, PublicKeyEd25519) =
+                from_bytes(&data).expect("Should return a valid result");
+
+            assert_eq!(signer, PublicKeyEd25519(PUBLIC_KEY_SIGNER_2));
+        }
+        _ => panic!("Should be rejected by the contract"),
+    }
+}
+
 #[test]
 fn test_get_name() {
     let (
@@ -1119,3 +1696,331 @@ fn test_upgrade_without_migration_function() {
         }
     );
 }
+
+/// Test that `atomicUpdateMany` reverts all upgrades if one of them fails.
+#[test]
+fn test_atomic_update_many() {
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    let history = get_registry_history(&chain, initialization_registry.contract_address);
+
+    let registrations = history.len();
+
+    let module = chain
+        .get_contract(initialization_umbrella_feeds.contract_address)
+        .expect("The umbrella_feeds contract should exist")
+        .module_reference;
+
+    let umbrella_feeds_upgrade = AtomicUpdateParam {
+        module,
+        migrate: None,
+        contract_address: initialization_umbrella_feeds.contract_address,
+    };
+
+    // Upgrading umbrella_feeds and a contract that does not exist fails as a whole.
+
+    let input_parameter = AtomicUpdateManyParams {
+        entries: vec![
+            umbrella_feeds_upgrade.clone(),
+            AtomicUpdateParam {
+                module,
+                migrate: None,
+                contract_address: ContractAddress {
+                    index: 9999999,
+                    subindex: 0,
+                },
+            },
+        ],
+    };
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(100000),
+            UpdateContractPayload {
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.atomicUpdateMany".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("Should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect_err("Should fail to upgrade a contract that does not exist");
+
+    // Checking that the registration of the umbrella_feeds contract was reverted.
+
+    let history = get_registry_history(&chain, initialization_registry.contract_address);
+
+    assert_eq!(history.len(), registrations);
+
+    // Upgrading umbrella_feeds alone succeeds.
+
+    let input_parameter = AtomicUpdateManyParams {
+        entries: vec![umbrella_feeds_upgrade],
+    };
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(100000),
+            UpdateContractPayload {
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.atomicUpdateMany".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("Should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect("Should be able to atomicUpdateMany");
+
+    let history = get_registry_history(&chain, initialization_registry.contract_address);
+
+    assert_eq!(history.len(), registrations + 1);
+    assert_eq!(
+        history[registrations].destination,
+        Some(initialization_umbrella_feeds.contract_address)
+    );
+}
+
+#[test]
+fn test_upgrade_staking_bank_natively() {
+    let (
+        mut chain,
+        _initialization_umbrella_feeds,
+        initialization_registry,
+        initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    let staking_bank = initialization_staking_bank.contract_address;
+
+    // Importing the staking_bank into the registry contract.
+
+    let input_parameter = ImportContractsParam {
+        entries: vec![staking_bank],
+    };
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.importContracts".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to import staking_bank into the registry");
+
+    // Natively upgrading the staking_bank via the registry.
+
+    let module = chain
+        .get_contract(staking_bank)
+        .expect("The staking_bank contract should exist")
+        .module_reference;
+
+    let input_parameter = AtomicUpdateParam {
+        module,
+        migrate: None,
+        contract_address: staking_bank,
+    };
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(100000),
+            UpdateContractPayload {
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.atomicUpdate".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("Should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect("Should be able to natively upgrade the staking_bank");
+
+    // Checking the logged `ContractUpgraded` event.
+
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let (_, registry_events) = events
+        .iter()
+        .rev()
+        .find(|(address, _)| *address == initialization_registry.contract_address)
+        .expect("The registry should log events");
+
+    let event = &registry_events[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 16, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: ContractUpgradedEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        ContractUpgradedEvent {
+            contract_address: staking_bank,
+            name: String::from("StakingBank"),
+            old_module: module,
+            new_module: module,
+            migrate: None,
+        },
+        "ContractUpgraded event is wrong"
+    );
+
+    // Checking that the staking bank address in the registry did not change.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.getAddress".to_string()),
+                message: OwnedParameter::from_serial(&String::from("StakingBank"))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query the staking bank address");
+
+    let address: ContractAddress =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(address, staking_bank);
+
+    // Checking that the state of the staking_bank was kept.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.getPublicKeys".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query the public keys");
+
+    let public_keys: Vec<PublicKeyEd25519> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        public_keys,
+        vec![
+            PublicKeyEd25519(PUBLIC_KEY_SIGNER_1),
+            PublicKeyEd25519(PUBLIC_KEY_SIGNER_2)
+        ]
+    );
+}
+
+#[test]
+fn test_rollback() {
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    let registry = initialization_registry.contract_address;
+    let umbrella_feeds = initialization_umbrella_feeds.contract_address;
+
+    let old_module = chain
+        .get_contract(umbrella_feeds)
+        .expect("The umbrella_feeds contract should exist")
+        .module_reference;
+
+    let rollback = RollbackParams {
+        contract_address: umbrella_feeds,
+        migrate: None,
+    };
+
+    // Checking that a contract that was not upgraded can not be rolled back.
+
+    let update = update_registry(&mut chain, registry, "rollback", &rollback)
+        .expect_err("Should fail to roll back a contract that was not upgraded");
+
+    assert_eq!(reject_reason(&update), Some(-23));
+
+    // Deploying a new umbrella_feeds module. A custom section is appended to the module so it gets a different module
+    // reference.
+
+    let mut module = module_load_v1("./umbrella_feeds.wasm.v1")
+        .expect("`umbrella_feeds.wasm.v1` module should be loaded");
+
+    let mut source: Vec<u8> = module.source.into();
+    source.extend_from_slice(&[0, 3, 2, b'v', b'2']);
+    module.source = source.into();
+
+    let new_module = chain
+        .module_deploy_v1(Signer::with_one_key(), ACC_ADDR_OWNER, module)
+        .expect("The new umbrella_feeds module deployment should always succeed")
+        .module_reference;
+
+    assert_ne!(old_module, new_module);
+
+    // Natively upgrading the umbrella_feeds contract records the old module.
+
+    update_registry(
+        &mut chain,
+        registry,
+        "atomicUpdate",
+        &AtomicUpdateParam {
+            module: new_module,
+            migrate: None,
+            contract_address: umbrella_feeds,
+        },
+    )
+    .expect("Should be able to natively upgrade the umbrella_feeds contract");
+
+    assert_eq!(
+        get_previous_modules(&chain, registry, umbrella_feeds),
+        vec![old_module]
+    );
+
+    // Rolling back restores the old module.
+
+    update_registry(&mut chain, registry, "rollback", &rollback)
+        .expect("Should be able to roll back the umbrella_feeds contract");
+
+    let module = chain
+        .get_contract(umbrella_feeds)
+        .expect("The umbrella_feeds contract should exist")
+        .module_reference;
+
+    assert_eq!(module, old_module);
+
+    assert_eq!(
+        get_previous_modules(&chain, registry, umbrella_feeds),
+        Vec::<ModuleReference>::new()
+    );
+
+    // Checking that the rollback can not be repeated.
+
+    let update = update_registry(&mut chain, registry, "rollback", &rollback)
+        .expect_err("Should fail to roll back a contract twice");
+
+    assert_eq!(reject_reason(&update), Some(-23));
+}